@@ -1,6 +1,6 @@
 use core::fmt;
 
-use pointplots::{Chart, PixelColor, Plot, Point, Shape};
+use pointplots::{AxisValue, Chart, PixelColor, Plot, Point, Shape};
 
 #[derive(Clone)]
 struct Temp(f64);
@@ -46,9 +46,9 @@ impl fmt::Display for Month {
     }
 }
 
-impl From<Month> for f64 {
-    fn from(month: Month) -> Self {
-        match month {
+impl AxisValue for Month {
+    fn to_f64(&self) -> f64 {
+        match self {
             Month::January => 0.,
             Month::Febuary => 1.,
             Month::March => 2.,
@@ -63,11 +63,9 @@ impl From<Month> for f64 {
             Month::December => 11.,
         }
     }
-}
 
-impl From<f64> for Month {
-    fn from(number: f64) -> Self {
-        match number.floor() as u32 {
+    fn label(value: f64) -> String {
+        let month = match value.floor() as u32 {
             0 => Month::January,
             1 => Month::Febuary,
             2 => Month::March,
@@ -80,20 +78,19 @@ impl From<f64> for Month {
             9 => Month::October,
             10 => Month::November,
             11 => Month::December,
-            _ => panic!("Can not convert {} to month.", number),
-        }
+            _ => panic!("Can not convert {} to month.", value),
+        };
+        month.to_string()
     }
 }
 
-impl From<Temp> for f64 {
-    fn from(temp: Temp) -> Self {
-        temp.0
+impl AxisValue for Temp {
+    fn to_f64(&self) -> f64 {
+        self.0
     }
-}
 
-impl From<f64> for Temp {
-    fn from(number: f64) -> Self {
-        Temp(number)
+    fn label(value: f64) -> String {
+        Temp(value).to_string()
     }
 }
 
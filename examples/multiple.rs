@@ -5,8 +5,8 @@ fn main() {
     // https://github.com/loony-bean/textplots-rs/issues/8
     println!("y = -x^2; y = x^2");
     Chart::<'_, f64, f64>::default()
-        .lineplot(&Shape::Continuous(Box::new(|x| (-x.powf(2.0)))))
-        .lineplot(&Shape::Continuous(Box::new(|x| (x.powf(2.0)))))
+        .lineplot(&Shape::Continuous(Box::new(|x| -x.powf(2.0))))
+        .lineplot(&Shape::Continuous(Box::new(|x| x.powf(2.0))))
         .display();
 
     // https://github.com/loony-bean/textplots-rs/issues/15
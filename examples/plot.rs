@@ -1,4 +1,4 @@
-use pointplots::{utils, Chart, PixelColor, Plot, Shape};
+use pointplots::{utils, Chart, Plot, Shape};
 
 fn main() {
     // You can pass any real value function.
@@ -39,7 +39,7 @@ fn main() {
         (10.0, -1.0),
     ];
 
-    let points = utils::f64s_into_points(&data);
+    let points = utils::into_points(data);
 
     println!("\ny = interpolated points");
     Chart::default().lineplot(&Shape::Lines(&points)).display();
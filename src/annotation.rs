@@ -0,0 +1,49 @@
+//! Reference lines, shaded ranges and text labels at data coordinates.
+//!
+//! Annotations are drawn beneath the shapes, into canvas cells the shapes leave empty,
+//! and the y-axis is scaled to show them.
+//!
+//! ```
+//! use pointplots::annotation::Annotation;
+//! use pointplots::{Chart, PixelColor, Plot, Point, Shape};
+//!
+//! let latency: Vec<_> = (0..60).map(|i| Point { x: i as f64, y: 120.0 + (i as f64 / 4.0).sin() * 40.0 }).collect();
+//!
+//! Chart::<'_, f64, f64>::new(120, 60, 0.0, 60.0)
+//!     .annotate(Annotation::HLine(200.0), PixelColor::Red, Some("SLO".into()))
+//!     .annotate(Annotation::XRange(20.0, 28.0), PixelColor::Yellow, Some("incident".into()))
+//!     .annotate(Annotation::Text(41.0, 190.0, "deploy".into()), PixelColor::Cyan, None)
+//!     .lineplot(&Shape::Lines(&latency))
+//!     .nice();
+//! ```
+
+use crate::AxisValue;
+
+/// Mark placed on a chart at data coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Annotation<T, U> {
+    /// Dashed line across the chart at a y value.
+    HLine(U),
+    /// Dashed line across the chart at an x value.
+    VLine(T),
+    /// Shading across the chart between two x values.
+    XRange(T, T),
+    /// Shading across the chart between two y values.
+    YRange(U, U),
+    /// Text starting at a point, moved up or down to the nearest rows with room for it.
+    Text(T, U, String),
+}
+
+impl<T, U> Annotation<T, U>
+where
+    U: AxisValue,
+{
+    /// Y values the chart should show.
+    pub(crate) fn ys(&self) -> Vec<f64> {
+        match self {
+            Annotation::HLine(y) | Annotation::Text(_, y, _) => vec![y.to_f64()],
+            Annotation::YRange(from, to) => vec![from.to_f64(), to.to_f64()],
+            Annotation::VLine(_) | Annotation::XRange(_, _) => vec![],
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Values along the axes of a chart.
+//!
+//! Any type implementing [`AxisValue`] can be used for the x or y values of points,
+//! which covers all primitive numbers. Other types map themselves to a position along
+//! the axis, and tell how positions are labeled.
+//!
+//! ```
+//! use pointplots::{AxisValue, Chart, Plot, Point, Shape};
+//!
+//! #[derive(Clone)]
+//! struct Celsius(f64);
+//!
+//! impl AxisValue for Celsius {
+//!     fn to_f64(&self) -> f64 {
+//!         self.0
+//!     }
+//!
+//!     fn label(value: f64) -> String {
+//!         format!("{:.0}°C", value)
+//!     }
+//! }
+//!
+//! let readings: Vec<Point<u32, Celsius>> = (0..24)
+//!     .map(|hour| Point { x: hour, y: Celsius(12.0 + (hour as f64 / 4.0).sin() * 6.0) })
+//!     .collect();
+//!
+//! Chart::<'_, u32, Celsius>::new(120, 60, 0.0, 23.0)
+//!     .lineplot(&Shape::Lines(&readings))
+//!     .display();
+//! ```
+
+/// Value along an axis of a chart.
+pub trait AxisValue: Clone {
+    /// Position of the value along the axis.
+    fn to_f64(&self) -> f64;
+
+    /// Label of a position along the axis.
+    fn label(value: f64) -> String;
+}
+
+/// Implements [`AxisValue`] for integers, labeled with the nearest integer.
+macro_rules! integer_axis_value {
+    ($($t:ty),*) => {
+        $(
+            impl AxisValue for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+
+                fn label(value: f64) -> String {
+                    (value.round() as $t).to_string()
+                }
+            }
+        )*
+    };
+}
+
+/// Implements [`AxisValue`] for floating point numbers, labeled with one decimal.
+macro_rules! float_axis_value {
+    ($($t:ty),*) => {
+        $(
+            impl AxisValue for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+
+                fn label(value: f64) -> String {
+                    format!("{:.1}", value)
+                }
+            }
+        )*
+    };
+}
+
+integer_axis_value!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+float_axis_value!(f32, f64);
@@ -0,0 +1,190 @@
+//! Arrangements of several bar series sharing the same chart.
+//!
+//! ```
+//! use pointplots::bars::BarLayout;
+//! use pointplots::{Chart, Plot, PixelColor, Point, Shape};
+//!
+//! let reads = [Point { x: 1.0, y: 30.0 }, Point { x: 2.0, y: 45.0 }, Point { x: 3.0, y: 20.0 }];
+//! let writes = [Point { x: 1.0, y: 10.0 }, Point { x: 2.0, y: 5.0 }, Point { x: 3.0, y: 25.0 }];
+//!
+//! let mut chart = Chart::<'_, f64, f64>::new(120, 60, 0.5, 3.5);
+//! chart
+//!     .bar_layout(BarLayout::Stacked)
+//!     .lineplot_with_tags(&Shape::Bars(&reads), Some("reads".into()), PixelColor::Blue)
+//!     .lineplot_with_tags(&Shape::Bars(&writes), Some("writes".into()), PixelColor::Red)
+//!     .nice();
+//! ```
+
+use std::cmp::Ordering;
+
+/// How bar series of a chart are arranged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BarLayout {
+    /// Every series is drawn on its own, each bar spanning from one point to the next
+    /// at the height of the latter (as for histograms), so series overlap.
+    #[default]
+    Overlap,
+    /// Bars centered at every x value, those of different series side by side.
+    Grouped,
+    /// Bars centered at every x value, those of different series stacked on each other.
+    Stacked,
+    /// Like `Stacked`, with every stack scaled to a total of 100 (percent).
+    Percent,
+}
+
+/// Bar in chart coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Bar {
+    pub left: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub top: f64,
+}
+
+/// Width taken by the bars at an x value, in distances between neighbour x values.
+const GROUP_WIDTH: f64 = 0.8;
+
+/// Arranges bar series of `(x, y)` points, returns the bars of every series in order.
+/// `Overlap` is not arranged and gives no bars.
+pub(crate) fn layout(series: &[Vec<(f64, f64)>], layout: BarLayout) -> Vec<Vec<Bar>> {
+    if layout == BarLayout::Overlap {
+        return vec![vec![]; series.len()];
+    }
+
+    // every x value gets a group as wide as a share of the narrowest distance between them
+    let mut xs: Vec<_> = series.iter().flatten().map(|&(x, _)| x).collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    xs.dedup();
+    let spacing = xs
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .fold(f64::INFINITY, f64::min);
+    let width = if spacing.is_finite() {
+        spacing * GROUP_WIDTH
+    } else {
+        GROUP_WIDTH
+    };
+    let position = |x: f64| {
+        xs.binary_search_by(|probe| probe.partial_cmp(&x).unwrap_or(Ordering::Equal))
+            .unwrap_or(0)
+    };
+
+    // percentages are taken of the sum of magnitudes at every x value
+    let mut sums = vec![0.0; xs.len()];
+    for &(x, y) in series.iter().flatten() {
+        sums[position(x)] += y.abs();
+    }
+
+    // stacks grow upwards from the positive values, and downwards from the negative ones
+    let (mut above, mut below) = (vec![0.0; xs.len()], vec![0.0; xs.len()]);
+
+    let n = series.len() as f64;
+    series
+        .iter()
+        .enumerate()
+        .map(|(k, points)| {
+            points
+                .iter()
+                .map(|&(x, y)| {
+                    let i = position(x);
+                    let (left, right) = (x - width / 2.0, x + width / 2.0);
+                    match layout {
+                        BarLayout::Grouped => Bar {
+                            left: left + width * k as f64 / n,
+                            right: left + width * (k + 1) as f64 / n,
+                            bottom: 0.0,
+                            top: y,
+                        },
+                        _ => {
+                            let y = if layout == BarLayout::Percent && sums[i] > 0.0 {
+                                y / sums[i] * 100.0
+                            } else {
+                                y
+                            };
+                            let stack = if y >= 0.0 {
+                                &mut above[i]
+                            } else {
+                                &mut below[i]
+                            };
+                            let bottom = *stack;
+                            *stack += y;
+                            Bar {
+                                left,
+                                right,
+                                bottom,
+                                top: *stack,
+                            }
+                        }
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(left: f64, right: f64, bottom: f64, top: f64) -> Bar {
+        Bar {
+            left,
+            right,
+            bottom,
+            top,
+        }
+    }
+
+    #[test]
+    fn grouped_bars_share_the_group_width() {
+        let series = vec![vec![(1.0, 3.0), (2.0, -1.0)], vec![(1.0, 2.0)]];
+        let bars = layout(&series, BarLayout::Grouped);
+
+        assert_eq!(
+            vec![
+                vec![bar(0.6, 1.0, 0.0, 3.0), bar(1.6, 2.0, 0.0, -1.0)],
+                vec![bar(1.0, 1.4, 0.0, 2.0)],
+            ],
+            bars
+        );
+    }
+
+    #[test]
+    fn stacks_grow_apart_from_positive_and_negative_values() {
+        let series = vec![
+            vec![(1.0, 3.0)],
+            vec![(1.0, -2.0)],
+            vec![(1.0, 4.0)],
+            vec![(1.0, -1.0)],
+        ];
+        let stacks: Vec<_> = layout(&series, BarLayout::Stacked)
+            .into_iter()
+            .map(|bars| (bars[0].bottom, bars[0].top))
+            .collect();
+
+        assert_eq!(
+            vec![(0.0, 3.0), (0.0, -2.0), (3.0, 7.0), (-2.0, -3.0)],
+            stacks
+        );
+    }
+
+    #[test]
+    fn percent_stacks_total_a_hundred() {
+        let series = vec![vec![(1.0, 1.0), (2.0, 6.0)], vec![(1.0, 3.0), (2.0, -2.0)]];
+        let bars = layout(&series, BarLayout::Percent);
+
+        assert_eq!((0.0, 25.0), (bars[0][0].bottom, bars[0][0].top));
+        assert_eq!((25.0, 100.0), (bars[1][0].bottom, bars[1][0].top));
+        // magnitudes add up to a hundred across both sides of the axis
+        assert_eq!((0.0, 75.0), (bars[0][1].bottom, bars[0][1].top));
+        assert_eq!((0.0, -25.0), (bars[1][1].bottom, bars[1][1].top));
+    }
+
+    #[test]
+    fn overlapping_series_are_not_arranged() {
+        let series = vec![vec![(1.0, 3.0)], vec![(1.0, 2.0)]];
+
+        let none: Vec<Vec<Bar>> = vec![vec![]; 2];
+        assert_eq!(none, layout(&series, BarLayout::Overlap));
+    }
+}
@@ -0,0 +1,201 @@
+//! Box-and-whisker plots of named samples.
+//!
+//! ```
+//! use pointplots::{boxplot::BoxPlot, Chart, Plot, Shape};
+//!
+//! let api = [12.0, 15.0, 14.0, 13.0, 40.0, 16.0, 14.5];
+//! let db = [3.0, 4.0, 3.5, 5.0, 4.2, 3.9];
+//! let samples = [("api", &api[..]), ("db", &db[..])];
+//!
+//! Chart::<'_, f64, f64>::new(120, 60, -0.5, 1.5)
+//!     .lineplot(&Shape::Boxes(BoxPlot::new(&samples)))
+//!     .display();
+//! ```
+
+use crate::utils::quantile_sorted as quantile;
+use std::cmp::Ordering;
+
+/// Direction in which the boxes are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Values along the y-axis, categories along the x-axis at `0, 1, 2, ...`.
+    Vertical,
+    /// Values along the x-axis, categories along the y-axis at `0, 1, 2, ...`.
+    Horizontal,
+}
+
+/// How far the whiskers reach.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Whiskers {
+    /// To the furthest values within `k` interquartile ranges of the box (Tukey uses 1.5),
+    /// values beyond are outliers.
+    Iqr(f64),
+    /// To the smallest and the largest values, there are no outliers.
+    MinMax,
+}
+
+/// Summary of a sample drawn as a box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxStats {
+    /// First quartile, bottom of the box.
+    pub q1: f64,
+    /// Median, line across the box.
+    pub median: f64,
+    /// Third quartile, top of the box.
+    pub q3: f64,
+    /// End of the lower whisker.
+    pub lower: f64,
+    /// End of the upper whisker.
+    pub upper: f64,
+    /// Values beyond the whiskers.
+    pub outliers: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Summarises a sample, ignoring values that are not finite.
+    /// Returns `None` if there are no values left.
+    ///
+    /// ```
+    /// # use pointplots::boxplot::{BoxStats, Whiskers};
+    /// let stats = BoxStats::new(&[1.0, 2.0, 3.0, 4.0, 5.0, 100.0], Whiskers::Iqr(1.5)).unwrap();
+    /// assert_eq!((2.25, 3.5, 4.75), (stats.q1, stats.median, stats.q3));
+    /// assert_eq!((1.0, 5.0), (stats.lower, stats.upper));
+    /// assert_eq!(vec![100.0], stats.outliers);
+    /// ```
+    pub fn new(sample: &[f64], whiskers: Whiskers) -> Option<Self> {
+        let mut sorted: Vec<_> = sample.iter().cloned().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let q1 = quantile(&sorted, 0.25);
+        let median = quantile(&sorted, 0.5);
+        let q3 = quantile(&sorted, 0.75);
+        let (low, high) = match whiskers {
+            Whiskers::Iqr(k) => (q1 - k * (q3 - q1), q3 + k * (q3 - q1)),
+            Whiskers::MinMax => (f64::NEG_INFINITY, f64::INFINITY),
+        };
+
+        let mut within = sorted.iter().filter(|&&v| v >= low && v <= high);
+        let lower = within.next().cloned().unwrap_or(q1);
+        let upper = within.next_back().cloned().unwrap_or(lower);
+
+        Some(BoxStats {
+            q1,
+            median,
+            q3,
+            lower,
+            upper,
+            outliers: sorted
+                .into_iter()
+                .filter(|&v| v < low || v > high)
+                .collect(),
+        })
+    }
+}
+
+/// Box plot of one or more named samples, one box per sample.
+///
+/// Boxes are placed at `0, 1, 2, ...` across the values axis, so the chart should span
+/// from `-0.5` to `n - 0.5` on that axis for `n` samples.
+#[derive(Debug, Clone)]
+pub struct BoxPlot<'a> {
+    pub(crate) samples: &'a [(&'a str, &'a [f64])],
+    pub(crate) orientation: Orientation,
+    pub(crate) whiskers: Whiskers,
+}
+
+/// Half of the box width, in distances between neighbour boxes.
+const HALF_WIDTH: f64 = 0.25;
+
+impl<'a> BoxPlot<'a> {
+    /// Creates vertical boxes with Tukey whiskers (1.5 interquartile ranges).
+    pub fn new(samples: &'a [(&'a str, &'a [f64])]) -> Self {
+        BoxPlot {
+            samples,
+            orientation: Orientation::Vertical,
+            whiskers: Whiskers::Iqr(1.5),
+        }
+    }
+
+    /// Draws the boxes horizontally.
+    pub fn horizontal(mut self) -> Self {
+        self.orientation = Orientation::Horizontal;
+        self
+    }
+
+    /// Sets how far the whiskers reach.
+    pub fn whiskers(mut self, whiskers: Whiskers) -> Self {
+        self.whiskers = whiskers;
+        self
+    }
+
+    /// Category names along with their position across the values axis.
+    pub(crate) fn categories(&self) -> Vec<(f64, String)> {
+        self.samples
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| (i as f64, name.to_string()))
+            .collect()
+    }
+
+    /// Summaries of the samples along with their position, empty samples are skipped.
+    pub(crate) fn stats(&self) -> Vec<(f64, BoxStats)> {
+        self.samples
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (_, sample))| {
+                BoxStats::new(sample, self.whiskers).map(|stats| (i as f64, stats))
+            })
+            .collect()
+    }
+
+    /// Range of values covered by the boxes, their whiskers and outliers.
+    pub(crate) fn bounds(&self) -> Vec<f64> {
+        self.stats()
+            .into_iter()
+            .flat_map(|(_, stats)| {
+                let mut values = stats.outliers;
+                values.push(stats.lower);
+                values.push(stats.upper);
+                values
+            })
+            .collect()
+    }
+
+    /// Outlines of the boxes and whiskers as segments, and outliers as points,
+    /// in chart coordinates.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn figures(&self) -> (Vec<((f64, f64), (f64, f64))>, Vec<(f64, f64)>) {
+        let mut segments = vec![];
+        let mut points = vec![];
+        let orient = |(at, value): (f64, f64)| match self.orientation {
+            Orientation::Vertical => (at, value),
+            Orientation::Horizontal => (value, at),
+        };
+        let mut segment = |a, b| segments.push((orient(a), orient(b)));
+
+        for (at, stats) in self.stats() {
+            let (left, right) = (at - HALF_WIDTH, at + HALF_WIDTH);
+            let (cap_left, cap_right) = (at - HALF_WIDTH / 2.0, at + HALF_WIDTH / 2.0);
+
+            // box and median
+            segment((left, stats.q1), (right, stats.q1));
+            segment((left, stats.q3), (right, stats.q3));
+            segment((left, stats.q1), (left, stats.q3));
+            segment((right, stats.q1), (right, stats.q3));
+            segment((left, stats.median), (right, stats.median));
+
+            // whiskers with caps
+            segment((at, stats.q3), (at, stats.upper));
+            segment((at, stats.q1), (at, stats.lower));
+            segment((cap_left, stats.upper), (cap_right, stats.upper));
+            segment((cap_left, stats.lower), (cap_right, stats.lower));
+
+            points.extend(stats.outliers.iter().map(|&value| orient((at, value))));
+        }
+
+        (segments, points)
+    }
+}
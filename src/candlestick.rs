@@ -0,0 +1,126 @@
+//! Candlestick charts of open/high/low/close values.
+//!
+//! ```
+//! use pointplots::candlestick::{Candle, Candlesticks};
+//! use pointplots::{Chart, Plot, Shape};
+//!
+//! let candles = [
+//!     Candle { x: 1.0, open: 10.0, high: 12.0, low: 9.0, close: 11.5 },
+//!     Candle { x: 2.0, open: 11.5, high: 11.8, low: 8.5, close: 9.0 },
+//!     Candle { x: 3.0, open: 9.0, high: 10.5, low: 8.8, close: 10.2 },
+//! ];
+//!
+//! Chart::<'_, f64, f64>::new(120, 60, 0.0, 4.0)
+//!     .lineplot(&Shape::Candlesticks(Candlesticks::new(&candles)))
+//!     .display();
+//! ```
+
+use crate::{AxisValue, PixelColor};
+
+/// Values of a single period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candle<T, U> {
+    /// Position of the period along the x-axis.
+    pub x: T,
+    /// First value of the period.
+    pub open: U,
+    /// Highest value of the period.
+    pub high: U,
+    /// Lowest value of the period.
+    pub low: U,
+    /// Last value of the period.
+    pub close: U,
+}
+
+/// Candle in chart coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Body {
+    pub x: f64,
+    /// Half of the body width.
+    pub half_width: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Body {
+    /// Tells if the period closed at or above its opening.
+    pub fn rising(&self) -> bool {
+        self.close >= self.open
+    }
+}
+
+/// Candlesticks of a series of periods: a wick from the low to the high value, and a body
+/// from the open to the close value, hollow when rising and filled when falling.
+#[derive(Debug, Clone)]
+pub struct Candlesticks<'a, T, U> {
+    pub(crate) candles: &'a [Candle<T, U>],
+    pub(crate) up: PixelColor,
+    pub(crate) down: PixelColor,
+}
+
+/// Body width, in distances between neighbour candles.
+const BODY_WIDTH: f64 = 0.6;
+
+impl<'a, T, U> Candlesticks<'a, T, U>
+where
+    T: AxisValue,
+    U: AxisValue,
+{
+    /// Creates candlesticks, green when rising and red when falling.
+    pub fn new(candles: &'a [Candle<T, U>]) -> Self {
+        Candlesticks {
+            candles,
+            up: PixelColor::Green,
+            down: PixelColor::Red,
+        }
+    }
+
+    /// Sets the colours of rising and falling candles.
+    pub fn colours(mut self, up: PixelColor, down: PixelColor) -> Self {
+        self.up = up;
+        self.down = down;
+        self
+    }
+
+    /// Candles in chart coordinates, those with values that are not finite are skipped.
+    pub(crate) fn bodies(&self) -> Vec<Body> {
+        let mut bodies: Vec<_> = self
+            .candles
+            .iter()
+            .map(|candle| Body {
+                x: candle.x.to_f64(),
+                half_width: 0.0,
+                open: candle.open.to_f64(),
+                high: candle.high.to_f64(),
+                low: candle.low.to_f64(),
+                close: candle.close.to_f64(),
+            })
+            .filter(|body| {
+                [body.x, body.open, body.high, body.low, body.close]
+                    .iter()
+                    .all(|v| v.is_finite())
+            })
+            .collect();
+
+        // bodies take a share of the narrowest distance between periods
+        let mut xs: Vec<_> = bodies.iter().map(|body| body.x).collect();
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let spacing = xs
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .filter(|d| *d > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        let half_width = if spacing.is_finite() {
+            spacing * BODY_WIDTH / 2.0
+        } else {
+            0.0
+        };
+        for body in &mut bodies {
+            body.half_width = half_width;
+        }
+
+        bodies
+    }
+}
@@ -0,0 +1,79 @@
+//! Measurements with uncertainties, drawn as error bars.
+//!
+//! ```
+//! use pointplots::errorbar::Measurement;
+//! use pointplots::{Chart, Plot, Point, Shape};
+//!
+//! let runs = [
+//!     Measurement::new(1.0, 12.0).yerr(1.5),
+//!     Measurement::new(2.0, 9.5).yerr(0.8),
+//!     Measurement::new(3.0, 7.2).yerr(2.1).xerr(0.2),
+//! ];
+//! let lower = [Point { x: 1.0, y: 10.0 }, Point { x: 3.0, y: 5.0 }];
+//! let upper = [Point { x: 1.0, y: 14.0 }, Point { x: 3.0, y: 9.5 }];
+//!
+//! Chart::<'_, f64, f64>::new(120, 60, 0.0, 4.0)
+//!     .lineplot(&Shape::Band(&lower, &upper))
+//!     .lineplot(&Shape::ErrorBars(&runs))
+//!     .display();
+//! ```
+
+use crate::AxisValue;
+
+/// Value with symmetric uncertainties along either axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement<T, U> {
+    /// Position along the x-axis.
+    pub x: T,
+    /// Position along the y-axis.
+    pub y: U,
+    /// Uncertainty of `x`, drawn as a horizontal bar from `x - xerr` to `x + xerr`.
+    pub xerr: Option<T>,
+    /// Uncertainty of `y`, drawn as a vertical bar from `y - yerr` to `y + yerr`.
+    pub yerr: Option<U>,
+}
+
+impl<T, U> Measurement<T, U>
+where
+    T: AxisValue,
+    U: AxisValue,
+{
+    /// Creates a measurement without uncertainties.
+    pub fn new(x: T, y: U) -> Self {
+        Measurement {
+            x,
+            y,
+            xerr: None,
+            yerr: None,
+        }
+    }
+
+    /// Sets the uncertainty along the x-axis.
+    pub fn xerr(mut self, xerr: T) -> Self {
+        self.xerr = Some(xerr);
+        self
+    }
+
+    /// Sets the uncertainty along the y-axis.
+    pub fn yerr(mut self, yerr: U) -> Self {
+        self.yerr = Some(yerr);
+        self
+    }
+
+    /// Position and the half-lengths of the bars in chart coordinates, zero without a finite
+    /// uncertainty so that it neither draws a bar nor stretches the y range.
+    pub(crate) fn values(&self) -> ((f64, f64), (f64, f64)) {
+        let err = |e: Option<f64>| e.filter(|e| e.is_finite()).map_or(0.0, f64::abs);
+
+        (
+            (self.x.to_f64(), self.y.to_f64()),
+            (
+                err(self.xerr.as_ref().map(T::to_f64)),
+                err(self.yerr.as_ref().map(U::to_f64)),
+            ),
+        )
+    }
+}
+
+/// Half of the length of the caps at the ends of error bars, in dots.
+pub(crate) const CAP: f64 = 2.0;
@@ -0,0 +1,387 @@
+//! Trend lines fitted to data points.
+//!
+//! ```
+//! use pointplots::fit::{Fit, Model};
+//! use pointplots::{utils, Chart, PixelColor, Plot, Shape};
+//!
+//! let data: Vec<_> = (0..40).map(|i| (i as f64, 3.0 + 0.5 * i as f64 + (i as f64).sin())).collect();
+//! let points = utils::f64s_into_points(&data);
+//! let trend = Fit::new(&data, Model::Linear).unwrap();
+//!
+//! Chart::<'_, f64, f64>::new(120, 60, 0.0, 40.0)
+//!     .lineplot(&Shape::Points(&points))
+//!     .lineplot_with_tags(&trend.shape(), Some(trend.label()), PixelColor::Red)
+//!     .nice();
+//! ```
+
+use crate::{path, Coordinates, Shape};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+/// Function fitted to the points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Model {
+    /// `y = a + b x`, by least squares.
+    Linear,
+    /// `y = a + b x + c x² + ...` of the given degree, by least squares.
+    Polynomial(usize),
+    /// `y = a e^(b x)`, by least squares on `ln y`, ignoring points with `y <= 0`.
+    Exponential,
+    /// `y = a + b ln x`, by least squares, ignoring points with `x <= 0`.
+    Logarithmic,
+    /// Local linear regressions over the given share of the points nearest to every point
+    /// (LOESS with tricube weights), joined by straight lines.
+    Loess(f64),
+}
+
+/// Model fitted to data points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fit {
+    model: Model,
+    /// Coefficients of the polynomial (of `ln y` for the exponential model) in the
+    /// standardized variable, lowest power first (none for LOESS).
+    coefficients: Vec<f64>,
+    /// Center and spread of the variable (`x`, or `ln x` for the logarithmic model),
+    /// which is standardized as `(x - center) / spread` to keep the equations well conditioned.
+    standard: (f64, f64),
+    /// Smoothed points of LOESS, sorted along the x-axis.
+    smoothed: Vec<(f64, f64)>,
+    r_squared: f64,
+}
+
+/// Reasons a model could not be fitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitError {
+    /// There are fewer usable points than coefficients of the model.
+    TooFewPoints,
+    /// The points do not determine a single model, e.g. they all share one x value.
+    Singular,
+    /// The model or its coefficient of determination is not finite at some of the points,
+    /// e.g. they overflow.
+    NotFinite,
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            FitError::TooFewPoints => "too few points to fit the model",
+            FitError::Singular => "the points do not determine a single model",
+            FitError::NotFinite => "the fitted model is not finite at every point",
+        })
+    }
+}
+
+impl Error for FitError {}
+
+impl Fit {
+    /// Fits the model to the points with finite coordinates.
+    ///
+    /// ```
+    /// # use pointplots::fit::{Fit, FitError, Model};
+    /// let fit = Fit::new(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)], Model::Linear).unwrap();
+    /// assert_eq!(7.0, fit.predict(3.0).round());
+    /// assert_eq!("y = 2.000x + 1.000", fit.equation());
+    /// assert!((fit.r_squared() - 1.0).abs() < 1e-9);
+    ///
+    /// assert_eq!(Err(FitError::TooFewPoints), Fit::new(&[(0.0, 1.0)], Model::Linear));
+    /// ```
+    ///
+    /// Large x values, such as timestamps, are fitted as well as small ones:
+    ///
+    /// ```
+    /// # use pointplots::fit::{Fit, Model};
+    /// let data: Vec<_> = (0..50).map(|i| (1.7e9 + 60.0 * i as f64, 2.0 * 1.01_f64.powi(i))).collect();
+    /// let fit = Fit::new(&data, Model::Exponential).unwrap();
+    /// assert!((fit.predict(1.7e9) - 2.0).abs() < 1e-6);
+    /// assert!((fit.r_squared() - 1.0).abs() < 1e-9);
+    ///
+    /// let fit = Fit::new(&[(1.7e9, 1.0), (1.7e9 + 1.0, 3.0), (1.7e9 + 2.0, 5.0)], Model::Linear).unwrap();
+    /// assert!((fit.predict(1.7e9 + 3.0) - 7.0).abs() < 1e-6);
+    /// ```
+    pub fn new(data: &[(f64, f64)], model: Model) -> Result<Self, FitError> {
+        let points: Vec<_> = data
+            .iter()
+            .cloned()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+
+        let mut fit = Fit {
+            model,
+            coefficients: vec![],
+            standard: (0.0, 1.0),
+            smoothed: vec![],
+            r_squared: f64::NAN,
+        };
+        match model {
+            Model::Linear => (fit.coefficients, fit.standard) = polynomial(&points, 1)?,
+            Model::Polynomial(degree) => {
+                (fit.coefficients, fit.standard) = polynomial(&points, degree)?
+            }
+            Model::Exponential => {
+                let logs: Vec<_> = points
+                    .iter()
+                    .filter(|(_, y)| *y > 0.0)
+                    .map(|&(x, y)| (x, y.ln()))
+                    .collect();
+                (fit.coefficients, fit.standard) = polynomial(&logs, 1)?;
+            }
+            Model::Logarithmic => {
+                let logs: Vec<_> = points
+                    .iter()
+                    .filter(|(x, _)| *x > 0.0)
+                    .map(|&(x, y)| (x.ln(), y))
+                    .collect();
+                (fit.coefficients, fit.standard) = polynomial(&logs, 1)?;
+            }
+            Model::Loess(span) => fit.smoothed = loess(&points, span)?,
+        }
+
+        // share of the variance of y explained by the model, over the points it covers
+        let covered: Vec<_> = points
+            .iter()
+            .filter(|(x, _)| model != Model::Logarithmic || *x > 0.0)
+            .collect();
+        let predictions: Vec<_> = covered.iter().map(|&&(x, _)| fit.predict(x)).collect();
+        if predictions.iter().any(|y| !y.is_finite()) {
+            return Err(FitError::NotFinite);
+        }
+        let n = covered.len() as f64;
+        let mean = covered.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let total: f64 = covered.iter().map(|(_, y)| (y - mean).powi(2)).sum();
+        let residual: f64 = covered
+            .iter()
+            .zip(&predictions)
+            .map(|(&&(_, y), prediction)| (y - prediction).powi(2))
+            .sum();
+        fit.r_squared = if total > 0.0 {
+            1.0 - residual / total
+        } else {
+            1.0
+        };
+        if !fit.r_squared.is_finite() {
+            return Err(FitError::NotFinite);
+        }
+
+        Ok(fit)
+    }
+
+    /// Value of the model at `x`, `NaN` where it is not defined.
+    pub fn predict(&self, x: f64) -> f64 {
+        let (center, spread) = self.standard;
+        let polynomial = |v: f64| {
+            let t = (v - center) / spread;
+            self.coefficients.iter().rev().fold(0.0, |y, a| y * t + a)
+        };
+        match self.model {
+            Model::Linear | Model::Polynomial(_) => polynomial(x),
+            Model::Exponential => polynomial(x).exp(),
+            Model::Logarithmic if x > 0.0 => polynomial(x.ln()),
+            Model::Logarithmic => f64::NAN,
+            Model::Loess(_) => path::interpolate(&self.smoothed, x).unwrap_or(f64::NAN),
+        }
+    }
+
+    /// Coefficient of determination of the fit, 1 when it goes through every point.
+    pub fn r_squared(&self) -> f64 {
+        self.r_squared
+    }
+
+    /// Formula of the fitted model.
+    pub fn equation(&self) -> String {
+        let c = unstandardized(&self.coefficients, self.standard);
+        match self.model {
+            Model::Linear | Model::Polynomial(_) => {
+                let mut terms = String::new();
+                for (power, a) in c.iter().enumerate().rev() {
+                    let variable = match power {
+                        0 => String::new(),
+                        1 => "x".to_string(),
+                        _ => format!("x{}", superscript(power)),
+                    };
+                    if terms.is_empty() {
+                        terms = format!("{}{}", number(*a), variable);
+                    } else {
+                        let sign = if *a < 0.0 { '-' } else { '+' };
+                        terms.push_str(&format!(" {} {}{}", sign, number(a.abs()), variable));
+                    }
+                }
+                format!("y = {}", terms)
+            }
+            // far from x = 0 the factor in front under- or overflows, the exponent doesn't
+            Model::Exponential if c[0].exp().is_normal() => {
+                format!("y = {}e^({}x)", number(c[0].exp()), number(c[1]))
+            }
+            Model::Exponential => format!("y = e^({} + {}x)", number(c[0]), number(c[1])),
+            Model::Logarithmic => format!("y = {} + {} ln x", number(c[0]), number(c[1])),
+            Model::Loess(span) => format!("LOESS, span {:.2}", span),
+        }
+    }
+
+    /// Equation and coefficient of determination, for legends.
+    pub fn label(&self) -> String {
+        format!("{} (R² = {:.3})", self.equation(), self.r_squared)
+    }
+
+    /// Continuous shape of the model, to overlay on the points.
+    pub fn shape<'a, P: Coordinates>(&self) -> Shape<'a, P> {
+        let fit = self.clone();
+        Shape::Continuous(Box::new(move |x| fit.predict(x)))
+    }
+}
+
+/// Coefficients of the least squares polynomial of `degree` in the standardized x, lowest
+/// power first, along with the center and spread of x that standardize it.
+fn polynomial(points: &[(f64, f64)], degree: usize) -> Result<(Vec<f64>, (f64, f64)), FitError> {
+    if points.len() <= degree {
+        return Err(FitError::TooFewPoints);
+    }
+
+    // powers of large x values differ by orders of magnitude, those of standardized ones don't
+    let center = points.iter().map(|(x, _)| x).sum::<f64>() / points.len() as f64;
+    let spread = points
+        .iter()
+        .map(|(x, _)| (x - center).abs())
+        .fold(0.0, f64::max);
+    let spread = if spread > 0.0 { spread } else { 1.0 };
+
+    // normal equations: sums of t powers times the coefficients equal sums of t powers times y
+    let size = degree + 1;
+    let mut matrix = vec![vec![0.0; size + 1]; size];
+    for &(x, y) in points {
+        let t = (x - center) / spread;
+        for (row, equation) in matrix.iter_mut().enumerate() {
+            for (column, sum) in equation.iter_mut().take(size).enumerate() {
+                *sum += t.powi((row + column) as i32);
+            }
+            equation[size] += t.powi(row as i32) * y;
+        }
+    }
+
+    Ok((solve(matrix)?, (center, spread)))
+}
+
+/// Solves a system of linear equations given as rows of coefficients followed by the constant,
+/// by Gaussian elimination. Fails if it has no single solution, that is when a pivot is
+/// negligible next to the largest coefficient.
+fn solve(mut matrix: Vec<Vec<f64>>) -> Result<Vec<f64>, FitError> {
+    let size = matrix.len();
+    let largest = matrix
+        .iter()
+        .flat_map(|equation| equation.iter().take(size))
+        .fold(0.0, |largest: f64, a| largest.max(a.abs()));
+    let tolerance = largest * 1e-12;
+
+    for column in 0..size {
+        let pivot = (column..size)
+            .max_by(|&a, &b| {
+                matrix[a][column]
+                    .abs()
+                    .partial_cmp(&matrix[b][column].abs())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .ok_or(FitError::Singular)?;
+        if matrix[pivot][column].abs() <= tolerance {
+            return Err(FitError::Singular);
+        }
+        matrix.swap(column, pivot);
+
+        let pivot = matrix[column].clone();
+        for (row, equation) in matrix.iter_mut().enumerate() {
+            if row != column {
+                let factor = equation[column] / pivot[column];
+                for (value, p) in equation.iter_mut().zip(&pivot).skip(column) {
+                    *value -= factor * p;
+                }
+            }
+        }
+    }
+
+    Ok((0..size).map(|i| matrix[i][size] / matrix[i][i]).collect())
+}
+
+/// Coefficients of a polynomial in `(v - center) / spread` expanded into powers of `v`.
+fn unstandardized(coefficients: &[f64], (center, spread): (f64, f64)) -> Vec<f64> {
+    // Horner's scheme, multiplying by (v - center) / spread at every step
+    let mut expanded = vec![0.0; coefficients.len()];
+    for &a in coefficients.iter().rev() {
+        let mut next = vec![0.0; expanded.len()];
+        for (power, e) in expanded.iter().enumerate() {
+            next[power] -= e * center / spread;
+            if power + 1 < next.len() {
+                next[power + 1] += e / spread;
+            }
+        }
+        next[0] += a;
+        expanded = next;
+    }
+
+    expanded
+}
+
+/// Coefficient with three decimals, or in scientific notation when too large or too small
+/// for them.
+fn number(a: f64) -> String {
+    if a == 0.0 || (1e-3..1e6).contains(&a.abs()) {
+        format!("{:.3}", a)
+    } else {
+        format!("{:.3e}", a)
+    }
+}
+
+/// LOESS smoothed value at every distinct x, sorted along the x-axis.
+fn loess(points: &[(f64, f64)], span: f64) -> Result<Vec<(f64, f64)>, FitError> {
+    if points.len() < 2 {
+        return Err(FitError::TooFewPoints);
+    }
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    let neighbours = ((span * points.len() as f64).ceil() as usize).clamp(2, points.len());
+
+    let mut smoothed: Vec<(f64, f64)> = vec![];
+    for &(x, _) in &points {
+        if smoothed.last().is_some_and(|&(last, _)| last == x) {
+            continue;
+        }
+
+        // tricube weights within the distance to the furthest of the nearest neighbours
+        let mut distances: Vec<_> = points.iter().map(|(px, _)| (px - x).abs()).collect();
+        distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let reach = distances[neighbours - 1].max(f64::EPSILON) * (1.0 + 1e-9);
+        let weighted = points.iter().map(|&(px, py)| {
+            let w = (1.0 - ((px - x).abs() / reach).powi(3)).max(0.0).powi(3);
+            (px, py, w)
+        });
+
+        // weighted linear regression of the offsets from x, its intercept is the value at x
+        let (mut sw, mut sd, mut sy, mut sdd, mut sdy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for (px, py, w) in weighted {
+            let d = px - x;
+            sw += w;
+            sd += w * d;
+            sy += w * py;
+            sdd += w * d * d;
+            sdy += w * d * py;
+        }
+        let denominator = sw * sdd - sd * sd;
+        let y = if denominator > sw * sdd * 1e-12 {
+            let slope = (sw * sdy - sd * sy) / denominator;
+            (sy - slope * sd) / sw
+        } else {
+            sy / sw
+        };
+        smoothed.push((x, y));
+    }
+
+    Ok(smoothed)
+}
+
+/// Number written in superscript digits.
+fn superscript(n: usize) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    n.to_string()
+        .chars()
+        .filter_map(|d| d.to_digit(10))
+        .map(|d| DIGITS[d as usize])
+        .collect()
+}
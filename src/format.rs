@@ -0,0 +1,101 @@
+//! Formatters of axis labels.
+//!
+//! Each function returns a formatter to lend to [`Chart::x_label_format`] or
+//! [`Chart::y_label_format`], any other `Fn(f64) -> String` works as well, including
+//! closures borrowing data that lives as long as the chart.
+//!
+//! [`Chart::x_label_format`]: crate::Chart::x_label_format
+//! [`Chart::y_label_format`]: crate::Chart::y_label_format
+//!
+//! ```
+//! use pointplots::{format, Chart, Plot, Point, Shape};
+//!
+//! let memory: Vec<_> = (0..60).map(|i| Point { x: i as f64, y: 2e8 + 1e6 * (i * i) as f64 }).collect();
+//! let unit = String::from("s");
+//!
+//! Chart::<'_, f64, f64>::new(120, 60, 0.0, 59.0)
+//!     .x_label_format(&|x| format!("{}{}", x, unit))
+//!     .y_label_format(&format::bytes(1))
+//!     .lineplot(&Shape::Lines(&memory))
+//!     .display();
+//! ```
+
+/// Value with the given number of decimals.
+///
+/// ```
+/// # use pointplots::format::fixed;
+/// assert_eq!("3.14", fixed(2)(3.14159));
+/// ```
+pub fn fixed(decimals: usize) -> impl Fn(f64) -> String {
+    move |value| format!("{:.*}", decimals, value)
+}
+
+/// Value scaled to thousands (`k`), millions (`M`), billions (`G`) or trillions (`T`).
+///
+/// ```
+/// # use pointplots::format::si;
+/// assert_eq!("1.5k", si(1)(1500.0));
+/// assert_eq!("-2.50M", si(2)(-2.5e6));
+/// assert_eq!("12.0", si(1)(12.0));
+/// ```
+pub fn si(decimals: usize) -> impl Fn(f64) -> String {
+    move |value| scaled(value, 1000.0, &["", "k", "M", "G", "T"], decimals)
+}
+
+/// Number of bytes scaled to kibibytes, mebibytes, gibibytes or tebibytes.
+///
+/// ```
+/// # use pointplots::format::bytes;
+/// assert_eq!("512.0 B", bytes(1)(512.0));
+/// assert_eq!("1.5 MiB", bytes(1)(1.5 * 1024.0 * 1024.0));
+/// ```
+pub fn bytes(decimals: usize) -> impl Fn(f64) -> String {
+    move |value| {
+        scaled(
+            value,
+            1024.0,
+            &[" B", " KiB", " MiB", " GiB", " TiB"],
+            decimals,
+        )
+    }
+}
+
+/// Fraction as a percentage.
+///
+/// ```
+/// # use pointplots::format::percent;
+/// assert_eq!("25%", percent(0)(0.25));
+/// ```
+pub fn percent(decimals: usize) -> impl Fn(f64) -> String {
+    move |value| format!("{:.*}%", decimals, value * 100.0)
+}
+
+/// Number of seconds in milliseconds, seconds, minutes or hours, whichever reads best.
+///
+/// ```
+/// # use pointplots::format::duration;
+/// assert_eq!("250ms", duration(0)(0.25));
+/// assert_eq!("4.5s", duration(1)(4.5));
+/// assert_eq!("2.0min", duration(1)(120.0));
+/// assert_eq!("1.5h", duration(1)(5400.0));
+/// ```
+pub fn duration(decimals: usize) -> impl Fn(f64) -> String {
+    move |value| match value.abs() {
+        v if v != 0.0 && v < 1.0 => format!("{:.*}ms", decimals, value * 1000.0),
+        v if v < 60.0 => format!("{:.*}s", decimals, value),
+        v if v < 3600.0 => format!("{:.*}min", decimals, value / 60.0),
+        _ => format!("{:.*}h", decimals, value / 3600.0),
+    }
+}
+
+/// Value divided by the largest power of `base` not above it, followed by the unit of that power.
+fn scaled(value: f64, base: f64, units: &[&str], decimals: usize) -> String {
+    let mut value = value;
+    let mut unit = 0;
+    while value.abs() >= base && unit + 1 < units.len() {
+        value /= base;
+        unit += 1;
+    }
+
+    format!("{:.*}{}", decimals, value, units[unit])
+}
@@ -0,0 +1,114 @@
+//! Grid lines at round values along the axes.
+//!
+//! ```
+//! use pointplots::grid::Grid;
+//! use pointplots::{Chart, PixelColor, Plot, Shape};
+//!
+//! Chart::<'_, f64, f64>::new(120, 60, -5.0, 5.0)
+//!     .grid(Grid::new().minor(2).colour(PixelColor::BrightBlack))
+//!     .lineplot(&Shape::Continuous(Box::new(|x| x.sin() * 4.0)))
+//!     .display();
+//! ```
+
+use crate::scale;
+use crate::PixelColor;
+
+/// Grid lines drawn beneath the data, in canvas cells the shapes leave empty.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid {
+    /// Draw lines across the x-axis, at round x values.
+    pub(crate) vertical: bool,
+    /// Draw lines across the y-axis, at round y values.
+    pub(crate) horizontal: bool,
+    /// Approximate number of major lines along each axis.
+    pub(crate) density: usize,
+    /// Minor lines between neighbour major lines, plus one.
+    pub(crate) minor: usize,
+    /// Distances between the dots of major and minor lines.
+    pub(crate) pattern: (u32, u32),
+    pub(crate) colour: PixelColor,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Grid {
+    /// Creates a dark grey grid of about 5 major lines along each axis, without minor lines.
+    pub fn new() -> Self {
+        Grid {
+            vertical: true,
+            horizontal: true,
+            density: 5,
+            minor: 1,
+            pattern: (2, 4),
+            colour: PixelColor::BrightBlack,
+        }
+    }
+
+    /// Draws only the vertical lines, at round x values.
+    pub fn vertical(mut self) -> Self {
+        self.vertical = true;
+        self.horizontal = false;
+        self
+    }
+
+    /// Draws only the horizontal lines, at round y values.
+    pub fn horizontal(mut self) -> Self {
+        self.vertical = false;
+        self.horizontal = true;
+        self
+    }
+
+    /// Sets the approximate number of major lines along each axis.
+    pub fn density(mut self, lines: usize) -> Self {
+        self.density = lines.max(1);
+        self
+    }
+
+    /// Splits the space between neighbour major lines into `divisions` with minor lines,
+    /// `1` draws no minor lines.
+    pub fn minor(mut self, divisions: usize) -> Self {
+        self.minor = divisions.max(1);
+        self
+    }
+
+    /// Sets the distances between the dots of major and minor lines (1 draws solid lines).
+    pub fn pattern(mut self, major: u32, minor: u32) -> Self {
+        self.pattern = (major.max(1), minor.max(1));
+        self
+    }
+
+    /// Sets the colour of the lines.
+    pub fn colour(mut self, colour: PixelColor) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    /// Values of the major and the minor lines within `min..=max`.
+    ///
+    /// ```
+    /// # use pointplots::grid::Grid;
+    /// let (major, minor) = Grid::new().minor(2).lines(0.0, 10.0);
+    /// assert_eq!(vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0], major);
+    /// assert_eq!(vec![1.0, 3.0, 5.0, 7.0, 9.0], minor);
+    /// ```
+    pub fn lines(&self, min: f64, max: f64) -> (Vec<f64>, Vec<f64>) {
+        let step = scale::step(min, max, self.density);
+        let major = scale::ticks(min, max, step);
+        let minor = if self.minor > 1 {
+            // skip the minor lines falling on major ones
+            let minor_step = step / self.minor as f64;
+            scale::ticks(min, max, minor_step)
+                .into_iter()
+                .filter(|v| (v / minor_step).round() as i64 % self.minor as i64 != 0)
+                .collect()
+        } else {
+            vec![]
+        };
+
+        (major, minor)
+    }
+}
@@ -0,0 +1,232 @@
+//! Heatmaps of a grid of values, drawn with coloured half-block characters.
+//!
+//! Every character cell shows two vertically stacked grid cells, so the grid looks
+//! roughly square in a terminal. Colours need a terminal with true colour support.
+//!
+//! ```
+//! use pointplots::heatmap::{Colormap, Heatmap};
+//!
+//! let values: Vec<Vec<f64>> = (0..20)
+//!     .map(|j| (0..40).map(|i| ((i as f64) / 6.0).sin() * ((j as f64) / 4.0).cos()).collect())
+//!     .collect();
+//!
+//! Heatmap::new(values).colormap(Colormap::Heat).display();
+//! ```
+
+use crate::PixelColor;
+use crate::{layout, utils};
+use colored::*;
+use std::ops::Range;
+
+/// Maps values between 0 and 1 to colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    /// Dark purple through blue and green to yellow, perceptually uniform.
+    Viridis,
+    /// Black through red and yellow to white.
+    Heat,
+    /// Black to white.
+    Grayscale,
+}
+
+impl Colormap {
+    /// Colours at evenly spaced positions, interpolated linearly in between.
+    fn stops(&self) -> &'static [(u8, u8, u8)] {
+        match self {
+            Colormap::Viridis => &[
+                (68, 1, 84),
+                (59, 82, 139),
+                (33, 145, 140),
+                (94, 201, 98),
+                (253, 231, 37),
+            ],
+            Colormap::Heat => &[(0, 0, 0), (200, 30, 0), (255, 200, 0), (255, 255, 255)],
+            Colormap::Grayscale => &[(0, 0, 0), (255, 255, 255)],
+        }
+    }
+
+    /// Colour of `t`, clamped to `[0, 1]`.
+    ///
+    /// ```
+    /// # use pointplots::{heatmap::Colormap, PixelColor};
+    /// assert_eq!(PixelColor::TrueColor { r: 255, g: 255, b: 255 }, Colormap::Grayscale.color(1.5));
+    /// ```
+    pub fn color(&self, t: f64) -> PixelColor {
+        let stops = self.stops();
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let position = t * (stops.len() - 1) as f64;
+        let i = (position.floor() as usize).min(stops.len() - 2);
+        let f = position - i as f64;
+
+        let (from, to) = (stops[i], stops[i + 1]);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+
+        PixelColor::TrueColor {
+            r: mix(from.0, to.0),
+            g: mix(from.1, to.1),
+            b: mix(from.2, to.2),
+        }
+    }
+}
+
+/// Heatmap of a grid of values.
+pub struct Heatmap {
+    /// Grid rows from the bottom up, like the y-axis.
+    values: Vec<Vec<f64>>,
+    colormap: Colormap,
+    /// Values mapped to the ends of the colormap (calculated automatically by default).
+    limits: Option<(f64, f64)>,
+    /// Ranges covered by the grid along the x-axis and y-axis, printed as labels.
+    ranges: Option<(Range<f64>, Range<f64>)>,
+    /// Characters per grid cell horizontally.
+    cell_width: usize,
+}
+
+/// Characters in the colour scale bar.
+const SCALE_WIDTH: usize = 24;
+
+impl Heatmap {
+    /// Creates a heatmap of a grid given as rows from the bottom up.
+    /// Values that are not finite are left blank.
+    pub fn new(values: Vec<Vec<f64>>) -> Self {
+        Heatmap {
+            values,
+            colormap: Colormap::Viridis,
+            limits: None,
+            ranges: None,
+            cell_width: 1,
+        }
+    }
+
+    /// Creates a 2D histogram of `(x, y)` points, see [`utils::histogram2d`](../utils/fn.histogram2d.html).
+    pub fn from_points(
+        data: &[(f64, f64)],
+        x: Range<f64>,
+        y: Range<f64>,
+        bins: (usize, usize),
+    ) -> Self {
+        let values = utils::histogram2d(data, x.clone(), y.clone(), bins);
+        Heatmap::new(values).ranges(x, y)
+    }
+
+    /// Sets the colormap, `Viridis` by default.
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Sets the values mapped to the ends of the colormap, values beyond are clamped.
+    pub fn limits(mut self, min: f64, max: f64) -> Self {
+        self.limits = Some((min, max));
+        self
+    }
+
+    /// Sets the ranges covered by the grid, printed as axis labels.
+    pub fn ranges(mut self, x: Range<f64>, y: Range<f64>) -> Self {
+        self.ranges = Some((x, y));
+        self
+    }
+
+    /// Sets the number of characters per grid cell horizontally.
+    pub fn cell_width(mut self, width: usize) -> Self {
+        self.cell_width = width.max(1);
+        self
+    }
+
+    /// Smallest and largest finite values of the grid (zero without any), unless set explicitly.
+    fn bounds(&self) -> (f64, f64) {
+        self.limits.unwrap_or_else(|| {
+            let (lo, hi) = self
+                .values
+                .iter()
+                .flatten()
+                .filter(|v| v.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                    (lo.min(v), hi.max(v))
+                });
+            if lo <= hi {
+                (lo, hi)
+            } else {
+                (0.0, 0.0)
+            }
+        })
+    }
+
+    /// Returns the rows of the heatmap, top to bottom.
+    pub fn rows(&self) -> Vec<String> {
+        let (lo, hi) = self.bounds();
+        let color = |v: f64| {
+            if v.is_finite() {
+                let t = if hi > lo { (v - lo) / (hi - lo) } else { 0.5 };
+                Some(self.colormap.color(t))
+            } else {
+                None
+            }
+        };
+        let columns = self.values.iter().map(Vec::len).max().unwrap_or(0);
+        let at = |j: usize, i: usize| {
+            self.values
+                .get(j)
+                .and_then(|row| row.get(i))
+                .cloned()
+                .unwrap_or(f64::NAN)
+        };
+
+        // pair grid rows from the top, the lowest cell is alone when their number is odd
+        let mut rows = vec![];
+        let mut top = self.values.len();
+        while top > 0 {
+            let (upper, lower) = (top - 1, top.checked_sub(2));
+            let mut row = String::new();
+            for i in 0..columns {
+                let lower = lower.map(|j| at(j, i)).unwrap_or(f64::NAN);
+                let cell = match (color(at(upper, i)), color(lower)) {
+                    (Some(up), Some(down)) => "▀".color(up).on_color(down).to_string(),
+                    (Some(up), None) => "▀".color(up).to_string(),
+                    (None, Some(down)) => "▄".color(down).to_string(),
+                    (None, None) => " ".to_string(),
+                };
+                row.push_str(&cell.repeat(self.cell_width));
+            }
+            rows.push(row);
+            top = top.saturating_sub(2);
+        }
+
+        rows
+    }
+
+    /// Returns the colour scale bar, with the values at its ends.
+    pub fn scale(&self) -> String {
+        let (lo, hi) = self.bounds();
+        let bar: String = (0..SCALE_WIDTH)
+            .map(|i| {
+                let t = i as f64 / (SCALE_WIDTH - 1) as f64;
+                "█".color(self.colormap.color(t)).to_string()
+            })
+            .collect();
+
+        format!("{:.1} {} {:.1}", lo, bar, hi)
+    }
+
+    /// Prints the heatmap, its axis labels and the colour scale bar.
+    pub fn display(&self) {
+        let rows = self.rows();
+        let last = rows.len().saturating_sub(1);
+        for (j, row) in rows.iter().enumerate() {
+            match &self.ranges {
+                Some((_, y)) if j == 0 => println!("{} {:.1}", row, y.end),
+                Some((_, y)) if j == last => println!("{} {:.1}", row, y.start),
+                _ => println!("{}", row),
+            }
+        }
+
+        if let Some((x, _)) = &self.ranges {
+            let width = self.values.iter().map(Vec::len).max().unwrap_or(0) * self.cell_width;
+            let (xmin, xmax) = (format!("{:.1}", x.start), format!("{:.1}", x.end));
+            println!("{}", layout::ends(&xmin, &xmax, width));
+        }
+
+        println!();
+        println!("{}", self.scale());
+    }
+}
@@ -0,0 +1,41 @@
+//! Placement of labels around the canvas, by the columns they take in a terminal.
+
+use std::cmp;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns taken by text in a terminal, wide characters counting twice.
+pub(crate) fn width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Text preceded by spaces up to `columns` wide.
+pub(crate) fn right(text: &str, columns: usize) -> String {
+    format!(
+        "{}{}",
+        " ".repeat(columns.saturating_sub(width(text))),
+        text
+    )
+}
+
+/// Labels of both ends of an axis `columns` wide: `min` starts at the left edge and `max`
+/// ends at the right edge, or follows `min` after a space when both don't fit.
+pub(crate) fn ends(min: &str, max: &str, columns: usize) -> String {
+    let gap = cmp::max(columns.saturating_sub(width(min) + width(max)), 1);
+    format!("{}{}{}", min, " ".repeat(gap), max)
+}
+
+/// Names centered on their columns, skipping those that would touch the name before.
+pub(crate) fn centered<'a>(names: impl IntoIterator<Item = (usize, &'a str)>) -> String {
+    let mut line = String::new();
+    let mut used = 0;
+    for (column, name) in names {
+        let start = column.saturating_sub(width(name) / 2);
+        if used == 0 || start > used {
+            line.push_str(&" ".repeat(start - used));
+            line.push_str(name);
+            used = start + width(name);
+        }
+    }
+
+    line
+}
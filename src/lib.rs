@@ -23,8 +23,8 @@ fn main() {
     println!("y = sin(x) / x");
 
     Chart::<'_, f64, f64>::default()
-    	.lineplot(&Shape::Continuous(Box::new(|x| x.sin() / x)))
-    	.display();
+        .lineplot(&Shape::Continuous(Box::new(|x| x.sin() / x)))
+        .display();
 }
 ```
 
@@ -53,32 +53,118 @@ You could also plot series of points. See [Shape](enum.Shape.html) and [examples
 <img src="https://github.com/loony-bean/textplots-rs/blob/master/doc/demo3.png?raw=true"/>
 */
 
+pub mod annotation;
+pub mod axis;
+pub mod bars;
+pub mod boxplot;
+pub mod candlestick;
+pub mod errorbar;
+pub mod fit;
+pub mod format;
+pub mod grid;
+pub mod heatmap;
+mod layout;
+mod path;
+mod sampling;
 pub mod scale;
+pub mod sparkline;
+#[cfg(feature = "serde")]
+pub mod spec;
+pub mod style;
+mod svg;
+pub mod terminal;
 pub mod utils;
 
+use annotation::Annotation;
+pub use axis::AxisValue;
+use bars::{Bar, BarLayout};
+use boxplot::{BoxPlot, Orientation};
+use candlestick::Candlesticks;
 use colored::*;
 use drawille::Canvas as BrailleCanvas;
 pub use drawille::PixelColor;
+use errorbar::Measurement;
+use grid::Grid;
 use scale::Scale;
-use std::any::type_name;
+use std::cmp;
+use std::collections::HashSet;
 use std::default::Default;
 use std::f64;
-use std::{cmp, fmt::Display};
+use style::{LineStyle, Style};
+use utils::Decimation;
 
 #[derive(Debug, PartialEq)]
-pub struct Point<
-    T: Into<f64> + From<f64> + Display + Clone,
-    U: Into<f64> + From<f64> + Display + Clone,
-> {
+pub struct Point<T: AxisValue, U: AxisValue> {
     pub x: T,
     pub y: U,
 }
 
+impl<T, U> From<(T, U)> for Point<T, U>
+where
+    T: AxisValue,
+    U: AxisValue,
+{
+    fn from((x, y): (T, U)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T, U> From<&(T, U)> for Point<T, U>
+where
+    T: AxisValue,
+    U: AxisValue,
+{
+    fn from((x, y): &(T, U)) -> Self {
+        Point {
+            x: x.clone(),
+            y: y.clone(),
+        }
+    }
+}
+
+impl<T> From<[T; 2]> for Point<T, T>
+where
+    T: AxisValue,
+{
+    fn from([x, y]: [T; 2]) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<&[T; 2]> for Point<T, T>
+where
+    T: AxisValue,
+{
+    fn from([x, y]: &[T; 2]) -> Self {
+        Point {
+            x: x.clone(),
+            y: y.clone(),
+        }
+    }
+}
+
+/// Types of the values along each axis, so shapes can carry richer data than points.
+pub trait Coordinates {
+    /// Type of x-axis values.
+    type X;
+    /// Type of y-axis values.
+    type Y;
+}
+
+impl<T, U> Coordinates for Point<T, U>
+where
+    T: AxisValue,
+    U: AxisValue,
+{
+    type X = T;
+    type Y = U;
+}
+
 /// Controls the drawing.
 pub struct Chart<'a, T, U>
 where
-    T: Into<f64> + From<f64> + Display + Clone,
-    U: Into<f64> + From<f64> + Display + Clone,
+    T: AxisValue,
+    U: AxisValue,
 {
     /// Canvas width in points.
     width: u32,
@@ -92,16 +178,39 @@ where
     ymin: f64,
     /// Y-axis end value (calculated automatically to display all the domain values).
     ymax: f64,
+    /// Fixed y-axis start and end values, replacing the calculated ones.
+    y_limits: (Option<f64>, Option<f64>),
+    /// Largest distance along the x-axis between points connected with a line.
+    gap: Option<f64>,
+    /// Arrangement of bar series.
+    bar_layout: BarLayout,
+    /// How long lines and steps are thinned out.
+    decimation: Decimation,
+    /// Grid lines drawn beneath the shapes.
+    grid: Option<Grid>,
+    /// Reference lines, shaded ranges and text labels, with their colours.
+    annotations: Vec<(Annotation<T, U>, PixelColor)>,
     /// Collection of shapes to be presented on the canvas.
-    shapes: Vec<(&'a Shape<'a, Point<T, U>>, PixelColor)>,
+    shapes: Vec<Series<'a, Point<T, U>>>,
     /// Labels associated with shapes on the screen (label will print out in associated PixelColor).
-    labels: Vec<(String, PixelColor)>,
+    labels: Vec<(String, Style)>,
+    /// Named positions printed instead of numeric labels on the x-axis.
+    x_categories: Vec<(f64, String)>,
+    /// Named positions printed instead of numeric labels on the y-axis.
+    y_categories: Vec<(f64, String)>,
+    /// Characters drawn over whole canvas cells, at dot coordinates within the cell.
+    overlay: Vec<(u32, u32, char, PixelColor)>,
+    /// Formatters of the x-axis and y-axis labels, replacing those of the value types.
+    /// They are borrowed like shapes: owning a `dyn Fn + 'a` would make dropping the chart
+    /// use `'a`, which `Plot` methods borrowing the chart for all of `'a` rule out.
+    x_format: Option<&'a dyn Fn(f64) -> String>,
+    y_format: Option<&'a dyn Fn(f64) -> String>,
     /// Underlying canvas object.
     canvas: BrailleCanvas,
 }
 
 /// Specifies different kinds of plotted data.
-pub enum Shape<'a, Point> {
+pub enum Shape<'a, Point: Coordinates> {
     /// Real value function.
     Continuous(Box<dyn Fn(f64) -> f64 + 'a>),
     /// Points of a scatter plot.
@@ -112,13 +221,53 @@ pub enum Shape<'a, Point> {
     Steps(&'a [Point]),
     /// Points represented with bars.
     Bars(&'a [Point]),
+    /// Box-and-whisker summaries of named samples.
+    Boxes(BoxPlot<'a>),
+    /// Open/high/low/close values of periods along the x-axis.
+    Candlesticks(Candlesticks<'a, Point::X, Point::Y>),
+    /// Measurements drawn as points with error bars.
+    ErrorBars(&'a [Measurement<Point::X, Point::Y>]),
+    /// Region between a lower and an upper series, shaded (e.g. a confidence interval).
+    Band(&'a [Point], &'a [Point]),
+}
+
+/// Shape presented on the canvas along with its drawing attributes.
+struct Series<'a, P: Coordinates> {
+    shape: &'a Shape<'a, P>,
+    style: Style,
+    /// Samples of a continuous shape, taken once (`NaN` values break the curve), or the
+    /// points of lines and steps decimated to the canvas width when there are more of them.
+    samples: Vec<(f64, f64)>,
+    /// Smallest and largest y values of the shape.
+    range: (f64, f64),
+}
+
+impl<T, U> Series<'_, Point<T, U>>
+where
+    T: AxisValue,
+    U: AxisValue,
+{
+    /// Points of the shape to draw: the samples if any were taken, or the data points.
+    fn data(&self) -> Vec<(f64, f64)> {
+        match self.shape {
+            Shape::Continuous(_) => self.samples.clone(),
+            Shape::Lines(_) | Shape::Steps(_) if !self.samples.is_empty() => self.samples.clone(),
+            Shape::Boxes(_) | Shape::Candlesticks(_) | Shape::ErrorBars(_) | Shape::Band(_, _) => {
+                vec![]
+            }
+            Shape::Points(dt) | Shape::Lines(dt) | Shape::Steps(dt) | Shape::Bars(dt) => dt
+                .iter()
+                .map(|point| (point.x.to_f64(), point.y.to_f64()))
+                .collect(),
+        }
+    }
 }
 
 /// Provides an interface for drawing plots.
 pub trait Plot<'a, T, U>
 where
-    T: Into<f64> + From<f64> + Display + Clone,
-    U: Into<f64> + From<f64> + Display + Clone,
+    T: AxisValue,
+    U: AxisValue,
 {
     /// Draws a [line chart](https://en.wikipedia.org/wiki/Line_chart) of points connected by straight line segments.
     fn lineplot(&'a mut self, shape: &'a Shape<Point<T, U>>) -> &'a mut Chart<'a, T, U>;
@@ -129,22 +278,29 @@ where
         label: Option<String>,
         colour: PixelColor,
     ) -> &'a mut Chart<'a, T, U>;
+    /// Tags drawing in a line chart, with an optional label and a specified style.
+    fn lineplot_with_style(
+        &'a mut self,
+        shape: &'a Shape<Point<T, U>>,
+        label: Option<String>,
+        style: Style,
+    ) -> &'a mut Chart<'a, T, U>;
 }
 
 impl<T, U> Default for Chart<'_, T, U>
 where
-    T: Into<f64> + From<f64> + Display + Clone,
-    U: Into<f64> + From<f64> + Display + Clone,
+    T: AxisValue,
+    U: AxisValue,
 {
     fn default() -> Self {
         Self::new(120, 60, -10.0, 10.0)
     }
 }
 
-impl<T, U> Chart<'_, T, U>
+impl<'a, T, U> Chart<'a, T, U>
 where
-    T: Into<f64> + From<f64> + Display + Clone,
-    U: Into<f64> + From<f64> + Display + Clone,
+    T: AxisValue,
+    U: AxisValue,
 {
     /// Creates a new `Chart` object.
     ///
@@ -165,14 +321,123 @@ where
             xmax,
             ymin: f64::INFINITY,
             ymax: f64::NEG_INFINITY,
+            y_limits: (None, None),
             width,
             height,
+            gap: None,
+            bar_layout: BarLayout::Overlap,
+            decimation: Decimation::MinMax,
+            grid: None,
+            annotations: vec![],
             shapes: vec![],
             labels: vec![],
+            x_categories: vec![],
+            y_categories: vec![],
+            overlay: vec![],
+            x_format: None,
+            y_format: None,
             canvas: BrailleCanvas::new(width, height),
         }
     }
 
+    /// Breaks lines and steps between consecutive points further apart than `gap` along the x-axis,
+    /// so missing data shows as a gap instead of a straight bridge.
+    pub fn max_gap(&mut self, gap: f64) -> &mut Self {
+        self.gap = Some(gap);
+        self
+    }
+
+    /// Fixes the start or the end of the y-axis, or both, instead of fitting them to the shapes.
+    /// Shapes reaching beyond are clipped.
+    pub fn y_range(&mut self, ymin: Option<f64>, ymax: Option<f64>) -> &mut Self {
+        self.y_limits = (ymin, ymax);
+        self.rescale();
+        self
+    }
+
+    /// Formats the x-axis labels with `format`, see [`format`](crate::format) for common ones.
+    /// Like shapes, the formatter is borrowed, so it may borrow data living as long as the chart.
+    pub fn x_label_format(&mut self, format: &'a dyn Fn(f64) -> String) -> &mut Self {
+        self.x_format = Some(format);
+        self
+    }
+
+    /// Formats the y-axis labels with `format`, see [`format`](crate::format) for common ones.
+    /// Like shapes, the formatter is borrowed, so it may borrow data living as long as the chart.
+    pub fn y_label_format(&mut self, format: &'a dyn Fn(f64) -> String) -> &mut Self {
+        self.y_format = Some(format);
+        self
+    }
+
+    /// Label of a position along the x-axis.
+    fn x_label(&self, x: f64) -> String {
+        match &self.x_format {
+            Some(format) => format(x),
+            None => T::label(x),
+        }
+    }
+
+    /// Label of a position along the y-axis.
+    fn y_label(&self, y: f64) -> String {
+        match &self.y_format {
+            Some(format) => format(y),
+            None => U::label(y),
+        }
+    }
+
+    /// Sets how lines and steps plotted from now on are thinned out when they have more points
+    /// than the canvas is wide.
+    ///
+    /// ```
+    /// use pointplots::utils::Decimation;
+    /// use pointplots::{Chart, Plot, Point, Shape};
+    ///
+    /// let signal: Vec<_> = (0..100_000)
+    ///     .map(|i| Point { x: i as f64, y: (i as f64 / 5000.0).sin() })
+    ///     .collect();
+    /// Chart::<'_, f64, f64>::new(120, 60, 0.0, 100_000.0)
+    ///     .decimation(Decimation::Lttb)
+    ///     .lineplot(&Shape::Lines(&signal))
+    ///     .display();
+    /// ```
+    pub fn decimation(&mut self, decimation: Decimation) -> &mut Self {
+        self.decimation = decimation;
+        self
+    }
+
+    /// Arranges bar series side by side or stacked, instead of overlapping them.
+    pub fn bar_layout(&mut self, layout: BarLayout) -> &mut Self {
+        self.bar_layout = layout;
+        self.rescale();
+        self
+    }
+
+    /// Draws grid lines at round values beneath the shapes.
+    pub fn grid(&mut self, grid: Grid) -> &mut Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    /// Marks a value, a range or a point of the chart, with an optional legend entry.
+    pub fn annotate(
+        &mut self,
+        annotation: Annotation<T, U>,
+        colour: PixelColor,
+        label: Option<String>,
+    ) -> &mut Self {
+        if let Some(label) = label {
+            let line = match annotation {
+                Annotation::HLine(_) | Annotation::VLine(_) => LineStyle::Dashed,
+                Annotation::XRange(_, _) | Annotation::YRange(_, _) => LineStyle::Thick,
+                Annotation::Text(_, _, _) => LineStyle::Solid,
+            };
+            self.labels.push((label, Style::new(colour).line(line)));
+        }
+        self.annotations.push((annotation, colour));
+        self.rescale();
+        self
+    }
+
     /// Displays bounding rect.
     fn borders(&mut self) {
         let w = self.width;
@@ -206,51 +471,264 @@ where
         }
     }
 
+    /// Canvas cells holding dots or overlay characters.
+    fn taken(&self) -> HashSet<(u32, u32)> {
+        let (columns, rows) = (self.width / 2 + 1, self.height / 4 + 1);
+        let canvas = &self.canvas;
+        let mut taken: HashSet<_> = (0..columns)
+            .flat_map(|c| (0..rows).map(move |r| (c, r)))
+            .filter(|&(c, r)| (0..2).any(|dx| (0..4).any(|dy| canvas.get(c * 2 + dx, r * 4 + dy))))
+            .collect();
+        taken.extend(self.overlay.iter().map(|&(i, j, _, _)| (i / 2, j / 4)));
+
+        taken
+    }
+
+    /// Draws the grid lines into the canvas cells left empty by the shapes.
+    fn grid_lines(&mut self, taken: &HashSet<(u32, u32)>) {
+        let grid = match self.grid {
+            Some(grid) => grid,
+            None => return,
+        };
+
+        let mut dots = vec![];
+        if grid.vertical && self.xmin < self.xmax {
+            let x_scale = Scale::new(self.xmin..self.xmax, 0.0..self.width as f64);
+            let (major, minor) = grid.lines(self.xmin, self.xmax);
+            for (xs, spacing) in [(major, grid.pattern.0), (minor, grid.pattern.1)] {
+                for x in xs {
+                    let i = x_scale.linear(x).round() as u32;
+                    dots.extend((0..=self.height).step_by(spacing as usize).map(|j| (i, j)));
+                }
+            }
+        }
+        if grid.horizontal && self.ymin < self.ymax {
+            let y_scale = Scale::new(self.ymin..self.ymax, 0.0..self.height as f64);
+            let (major, minor) = grid.lines(self.ymin, self.ymax);
+            for (ys, spacing) in [(major, grid.pattern.0), (minor, grid.pattern.1)] {
+                for y in ys {
+                    let j = (self.height as f64 - y_scale.linear(y)).round() as u32;
+                    dots.extend((0..=self.width).step_by(spacing as usize).map(|i| (i, j)));
+                }
+            }
+        }
+
+        for (i, j) in dots {
+            if !taken.contains(&(i / 2, j / 4)) {
+                self.canvas.set_colored(i, j, grid.colour);
+            }
+        }
+    }
+
+    /// Draws reference lines and shaded ranges into the canvas cells left empty by the shapes,
+    /// and text labels into the nearest empty cells.
+    fn annotations(&mut self, taken: &HashSet<(u32, u32)>) {
+        let x_scale = Scale::new(self.xmin..self.xmax, 0.0..self.width as f64);
+        let y_scale = Scale::new(self.ymin..self.ymax, 0.0..self.height as f64);
+        let height = self.height as f64;
+        let (xmin, xmax, ymin, ymax) = (self.xmin, self.xmax, self.ymin, self.ymax);
+        let i = |x: f64| x_scale.linear(x).round() as u32;
+        let j = |y: f64| (height - y_scale.linear(y)).round() as u32;
+        let dashes = LineStyle::Dashed.pattern();
+        let dashed = |k: &u32| dashes[*k as usize % dashes.len()];
+        let shaded = |i: u32, j: u32| i.is_multiple_of(2) && j.is_multiple_of(2);
+
+        let mut dots = vec![];
+        let mut texts = vec![];
+        for (annotation, colour) in &self.annotations {
+            let colour = *colour;
+            match annotation {
+                Annotation::HLine(y) => {
+                    let y: f64 = y.to_f64();
+                    if y >= ymin && y <= ymax {
+                        let j = j(y);
+                        dots.extend((0..=self.width).filter(dashed).map(|i| (i, j, colour)));
+                    }
+                }
+                Annotation::VLine(x) => {
+                    let x: f64 = x.to_f64();
+                    if x >= xmin && x <= xmax {
+                        let i = i(x);
+                        dots.extend((0..=self.height).filter(dashed).map(|j| (i, j, colour)));
+                    }
+                }
+                Annotation::XRange(from, to) => {
+                    let (from, to): (f64, f64) = (from.to_f64(), to.to_f64());
+                    let (from, to) = (from.min(to), from.max(to));
+                    if to >= xmin && from <= xmax {
+                        for i in i(from)..=i(to) {
+                            let column = (0..=self.height).filter(|&j| shaded(i, j));
+                            dots.extend(column.map(|j| (i, j, colour)));
+                        }
+                    }
+                }
+                Annotation::YRange(from, to) => {
+                    let (from, to): (f64, f64) = (from.to_f64(), to.to_f64());
+                    if from.max(to) >= ymin && from.min(to) <= ymax {
+                        for j in j(from.max(to))..=j(from.min(to)) {
+                            let row = (0..=self.width).filter(|&i| shaded(i, j));
+                            dots.extend(row.map(|i| (i, j, colour)));
+                        }
+                    }
+                }
+                Annotation::Text(x, y, text) => {
+                    let (x, y): (f64, f64) = (x.to_f64(), y.to_f64());
+                    if x.is_finite() && y.is_finite() {
+                        texts.push((i(x) / 2, j(y) / 4, text.clone(), colour));
+                    }
+                }
+            }
+        }
+
+        for (i, j, colour) in dots {
+            if !taken.contains(&(i / 2, j / 4)) {
+                self.canvas.set_colored(i, j, colour);
+            }
+        }
+
+        // texts move to the nearest rows where they fit, and are dropped if there are none
+        let (columns, rows) = (self.width / 2 + 1, self.height / 4 + 1);
+        let mut used = taken.clone();
+        for (column, row, text, colour) in texts {
+            let length = text.chars().count() as u32;
+            if length == 0 || length > columns {
+                continue;
+            }
+            let start = cmp::min(column, columns - length);
+            let fits = |row: u32| (start..start + length).all(|c| !used.contains(&(c, row)));
+            let nearest = (0..rows)
+                .flat_map(|d| vec![row.checked_sub(d), Some(row + d)])
+                .flatten()
+                .filter(|&r| r < rows)
+                .find(|&r| fits(r));
+
+            if let Some(row) = nearest {
+                for (c, ch) in (start..).zip(text.chars()) {
+                    used.insert((c, row));
+                    self.overlay.push((c * 2, row * 4, ch, colour));
+                }
+            }
+        }
+    }
+
     /// Prints canvas content.
     pub fn display(&mut self) {
         self.figures();
+        let taken = self.taken();
+        self.grid_lines(&taken);
+        self.annotations(&taken);
         self.axis();
+        self.overlay();
 
-        let frame = self.canvas.frame();
-        let rows = frame.split('\n').count();
-        for (i, row) in frame.split('\n').enumerate() {
-            if i == 0 {
-                let ymax: U = self.ymax.into();
-                println!("{0} {1:.1}", row, ymax);
-            } else if i == (rows - 1) {
-                let ymin: U = self.ymin.into();
-                println!("{0} {1:.1}", row, ymin);
-            } else {
+        let rows = self.rows();
+        let mut row_labels = vec![String::new(); rows.len()];
+        if self.y_categories.is_empty() {
+            // values aligned to the right of a margin as wide as the widest of them
+            let (ymax, ymin) = (self.y_label(self.ymax), self.y_label(self.ymin));
+            let margin = self.y_label_columns().saturating_sub(1);
+            row_labels[0] = layout::right(&ymax, margin);
+            row_labels[rows.len() - 1] = layout::right(&ymin, margin);
+        } else {
+            let y_scale = Scale::new(self.ymin..self.ymax, 0.0..self.height as f64);
+            for (y, name) in &self.y_categories {
+                let row = (self.height as f64 - y_scale.linear(*y)) as usize / 4;
+                row_labels[cmp::min(row, rows.len() - 1)] = name.clone();
+            }
+        }
+        for (row, label) in rows.iter().zip(row_labels) {
+            if label.is_empty() {
                 println!("{}", row);
+            } else {
+                println!("{} {}", row, label);
             }
         }
 
-        let xmin: T = self.xmin.into();
-        let xmax: T = self.xmax.into();
-        if type_name::<T>() == type_name::<f64>() {
-            println!(
-                "{0: <width$.1}{1:.1}",
-                self.xmin,
-                self.xmax,
-                width = (self.width as usize) / 2 - 3
-            );
+        let x_scale = Scale::new(self.xmin..self.xmax, 0.0..self.width as f64);
+        if self.x_categories.is_empty() {
+            // the canvas prints one character past its width
+            let columns = self.width as usize / 2 + 1;
+            let (xmin, xmax) = (self.x_label(self.xmin), self.x_label(self.xmax));
+            println!("{}", layout::ends(&xmin, &xmax, columns));
         } else {
-            // properly balance x labels when printing
-            let label_lengths: usize = xmin.to_string().len() + xmax.to_string().len();
-            println!(
-                "{0: <spacing$}{1:spacing$}{2:}",
-                xmin,
-                " ",
-                xmax,
-                spacing = (self.width as usize) / 2 - label_lengths
-            );
+            let names = self
+                .x_categories
+                .iter()
+                .map(|(x, name)| (x_scale.linear(*x) as usize / 2, name.as_str()));
+            println!("{}", layout::centered(names));
+        }
+    }
+    /// Columns printed by `display` to the right of the canvas: a space and the widest
+    /// y-axis label, or none without labels. They depend on the y range, so on the shapes.
+    ///
+    /// ```
+    /// # use pointplots::{Chart, Plot, Shape};
+    /// let shape = Shape::Continuous(Box::new(|x| 1000.0 * x));
+    /// let mut chart = Chart::<'_, f64, f64>::default();
+    /// // a space, then "-10000.0"
+    /// assert_eq!(9, chart.lineplot(&shape).y_label_columns());
+    /// ```
+    pub fn y_label_columns(&self) -> usize {
+        let widest = if self.y_categories.is_empty() {
+            let (ymax, ymin) = (self.y_label(self.ymax), self.y_label(self.ymin));
+            cmp::max(layout::width(&ymax), layout::width(&ymin))
+        } else {
+            self.y_categories
+                .iter()
+                .map(|(_, name)| layout::width(name))
+                .max()
+                .unwrap_or(0)
+        };
+
+        if widest > 0 {
+            widest + 1
+        } else {
+            0
         }
     }
+
+    /// Renders the chart as a standalone SVG image, with the same shapes, annotations, grid,
+    /// labels and legend drawn as vector lines on a dark background, a canvas dot taking 4 pixels.
+    ///
+    /// ```
+    /// use pointplots::{Chart, PixelColor, Plot, Point, Shape};
+    ///
+    /// let load: Vec<_> = (0..48).map(|i| Point { x: i as f64, y: 40.0 + (i as f64 / 6.0).sin() * 25.0 }).collect();
+    ///
+    /// let svg = Chart::<'_, f64, f64>::new(120, 60, 0.0, 47.0)
+    ///     .lineplot_with_tags(&Shape::Lines(&load), Some("load".into()), PixelColor::Cyan)
+    ///     .svg();
+    /// assert!(svg.starts_with("<svg") && svg.contains("<polyline"));
+    /// ```
+    ///
+    /// The same chart always renders the same image, and elements at non-finite coordinates
+    /// are left out.
+    ///
+    /// ```
+    /// use pointplots::annotation::Annotation;
+    /// use pointplots::{Chart, PixelColor, Plot, Shape};
+    ///
+    /// let shape = Shape::Continuous(Box::new(|x| x * x));
+    /// let svg = |ymark| {
+    ///     Chart::<'_, f64, f64>::new(60, 40, -1.0, 1.0)
+    ///         .annotate(Annotation::HLine(ymark), PixelColor::Red, None)
+    ///         .lineplot(&shape)
+    ///         .svg()
+    /// };
+    /// assert_eq!(svg(0.5), svg(0.5));
+    /// assert!(!svg(f64::NAN).contains("NaN"));
+    /// ```
+    pub fn svg(&self) -> String {
+        svg::render(self)
+    }
+
     /// Prints a legend that gives names to shapes and aligns them to colours.
     pub fn legends(&mut self) {
-        println!("");
-        for label in &self.labels {
-            println!("{}", format!("{}: ⠉⠉⠉", label.0).color(label.1));
+        println!();
+        for (label, style) in &self.labels {
+            println!(
+                "{}",
+                format!("{}: {}", label, style.line.swatch()).color(style.colour)
+            );
         }
     }
     /// Prints canvas content with some additional visual elements (like borders and a legend).
@@ -273,94 +751,329 @@ where
         }
     }
 
+    /// Arranges the bar series, returning the bars of every one in order.
+    fn arrange_bars(&self) -> Vec<Vec<Bar>> {
+        let bar_series: Vec<Vec<(f64, f64)>> = self
+            .shapes
+            .iter()
+            .filter_map(|series| match series.shape {
+                Shape::Bars(dt) => Some(
+                    dt.iter()
+                        .map(|point| (point.x.to_f64(), point.y.to_f64()))
+                        .filter(|&(x, y): &(f64, f64)| {
+                            x >= self.xmin && x <= self.xmax && y.is_finite()
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect();
+
+        bars::layout(&bar_series, self.bar_layout)
+    }
+
+    /// Fits the y range to the shapes and annotations, unless it is fixed. Arranged bars are
+    /// taken as they are drawn, since stacks may reach beyond the values of any series.
+    fn rescale(&mut self) {
+        let arranged = self.bar_layout != BarLayout::Overlap;
+        let (mut ymin, mut ymax) = (f64::INFINITY, f64::NEG_INFINITY);
+        for series in &self.shapes {
+            if !(arranged && matches!(series.shape, Shape::Bars(_))) {
+                ymin = ymin.min(series.range.0);
+                ymax = ymax.max(series.range.1);
+            }
+        }
+        if arranged {
+            for bar in self.arrange_bars().iter().flatten() {
+                ymin = ymin.min(bar.bottom).min(bar.top);
+                ymax = ymax.max(bar.bottom).max(bar.top);
+            }
+        }
+        for (annotation, _) in &self.annotations {
+            for y in annotation.ys().into_iter().filter(|y| y.is_finite()) {
+                ymin = ymin.min(y);
+                ymax = ymax.max(y);
+            }
+        }
+
+        self.ymin = self.y_limits.0.unwrap_or(ymin);
+        self.ymax = self.y_limits.1.unwrap_or(ymax);
+    }
+
     // Show figures.
     pub fn figures(&mut self) {
-        for shape in &self.shapes {
+        // overlay characters are placed anew on every render
+        self.overlay.clear();
+        let mut bars = self.arrange_bars().into_iter();
+
+        for series in &self.shapes {
             let x_scale = Scale::new(self.xmin..self.xmax, 0.0..self.width as f64);
             let y_scale = Scale::new(self.ymin..self.ymax, 0.0..self.height as f64);
+            let color = series.style.colour;
+            let mut pen = Pen::new(series.style.line, self.width, self.height);
 
             // translate (x, y) points into screen coordinates
-            let points: Vec<_> = match shape {
-                (Shape::Continuous(f), color) => (0..self.width)
-                    .filter_map(|i| {
-                        let x = x_scale.inv_linear(i as f64);
-                        let y = f(x);
-                        if y.is_normal() {
-                            let j = y_scale.linear(y).round();
-                            Some((i, self.height - j as u32, color.clone()))
-                        } else {
-                            None
+            let height = self.height as f64;
+            let screen =
+                |(x, y): (f64, f64)| (x_scale.extrapolate(x), height - y_scale.extrapolate(y));
+            let (min, max) = ((0.0, 0.0), (self.width as f64, height));
+            let data = series.data();
+
+            // display segments
+            match series.shape {
+                Shape::Continuous(_) => {
+                    // samples are broken where the function is not defined
+                    for run in path::split(&data, None) {
+                        let run: Vec<_> = run.into_iter().map(screen).collect();
+                        for line in path::clip(&run, min, max) {
+                            pen.polyline(&mut self.canvas, &line, color);
                         }
-                    })
-                    .collect(),
-                (Shape::Points(dt), color)
-                | (Shape::Lines(dt), color)
-                | (Shape::Steps(dt), color)
-                | (Shape::Bars(dt), color) => dt
-                    .iter()
-                    .filter_map(|point| {
-                        let x: f64 = point.x.clone().into();
-                        let y: f64 = point.y.clone().into();
-                        let i = x_scale.linear(x).round() as u32;
-                        let j = y_scale.linear(y).round() as u32;
-                        if i <= self.width && j <= self.height {
-                            Some((i, self.height - j, color.clone()))
+                    }
+                }
+                Shape::Lines(_) => {
+                    for run in path::split(&data, self.gap) {
+                        let run: Vec<_> = run.into_iter().map(screen).collect();
+                        for line in path::clip(&run, min, max) {
+                            pen.polyline(&mut self.canvas, &line, color);
+                        }
+                    }
+                }
+                Shape::Points(_) => {
+                    let marker = series.style.marker;
+                    let inside = |i: f64, j: f64| {
+                        (min.0..=max.0).contains(&i) && (min.1..=max.1).contains(&j)
+                    };
+                    for point in data {
+                        let (i, j) = screen(point);
+                        if !inside(i, j) {
+                            continue;
+                        }
+                        if series.style.marks_text() {
+                            let (i, j) = (i.round() as u32, j.round() as u32);
+                            self.overlay.push((i, j, marker.glyph(), color));
+                            continue;
+                        }
+                        for (dx, dy) in marker.dots() {
+                            let (i, j) = (i.round() + dx as f64, j.round() + dy as f64);
+                            if inside(i, j) {
+                                self.canvas.set_colored(i as u32, j as u32, color);
+                            }
+                        }
+                    }
+                }
+                Shape::Steps(_) => {
+                    for run in path::split(&data, self.gap) {
+                        let steps: Vec<_> = path::steps(&run).into_iter().map(screen).collect();
+                        for line in path::clip(&steps, min, max) {
+                            pen.polyline(&mut self.canvas, &line, color);
+                        }
+                    }
+                }
+                Shape::Boxes(boxes) => {
+                    let (segments, outliers) = boxes.figures();
+                    for (a, b) in segments {
+                        if let Some((a, b)) = path::clip_segment(screen(a), screen(b), min, max) {
+                            polyline(&mut self.canvas, &[a, b], color);
+                        }
+                    }
+                    for point in outliers {
+                        let (i, j) = screen(point);
+                        if (0.0..=self.width as f64).contains(&i) && (0.0..=height).contains(&j) {
+                            self.canvas
+                                .set_colored(i.round() as u32, j.round() as u32, color);
+                        }
+                    }
+                }
+                Shape::Candlesticks(candles) => {
+                    for body in candles.bodies() {
+                        let colour = if body.rising() {
+                            candles.up
+                        } else {
+                            candles.down
+                        };
+                        let (left, top) =
+                            screen((body.x - body.half_width, body.open.max(body.close)));
+                        let (right, bottom) =
+                            screen((body.x + body.half_width, body.open.min(body.close)));
+
+                        // wick, then a hollow body when rising and a filled one when falling
+                        let mut segments =
+                            vec![(screen((body.x, body.low)), screen((body.x, body.high)))];
+                        if body.rising() {
+                            segments.push(((left, top), (right, top)));
+                            segments.push(((left, bottom), (right, bottom)));
+                            segments.push(((left, top), (left, bottom)));
+                            segments.push(((right, top), (right, bottom)));
                         } else {
-                            None
+                            let mut i = left.round();
+                            while i <= right.round() {
+                                segments.push(((i, top), (i, bottom)));
+                                i += 1.0;
+                            }
                         }
-                    })
-                    .collect(),
-            };
 
-            // display segments
-            match shape {
-                (Shape::Continuous(_), _) | (Shape::Lines(_), _) => {
-                    for pair in points.windows(2) {
-                        let (x1, y1, color) = pair[0];
-                        let (x2, y2, _) = pair[1];
+                        for (a, b) in segments {
+                            if let Some((a, b)) = path::clip_segment(a, b, min, max) {
+                                polyline(&mut self.canvas, &[a, b], colour);
+                            }
+                        }
+                    }
+                }
+                Shape::ErrorBars(measurements) => {
+                    for measurement in measurements.iter() {
+                        let ((x, y), (xerr, yerr)) = measurement.values();
+                        if !x.is_finite() || !y.is_finite() {
+                            continue;
+                        }
 
-                        self.canvas.line_colored(x1, y1, x2, y2, color);
+                        // the point itself, then bars with caps at both ends
+                        let (i, j) = screen((x, y));
+                        let cap = errorbar::CAP;
+                        let mut segments = vec![((i, j), (i, j))];
+                        if yerr > 0.0 {
+                            let top = screen((x, y + yerr)).1;
+                            let bottom = screen((x, y - yerr)).1;
+                            segments.push(((i, top), (i, bottom)));
+                            for end in [top, bottom] {
+                                segments.push(((i - cap, end), (i + cap, end)));
+                            }
+                        }
+                        if xerr > 0.0 {
+                            let left = screen((x - xerr, y)).0;
+                            let right = screen((x + xerr, y)).0;
+                            segments.push(((left, j), (right, j)));
+                            for end in [left, right] {
+                                segments.push(((end, j - cap), (end, j + cap)));
+                            }
+                        }
+
+                        for (a, b) in segments {
+                            if let Some((a, b)) = path::clip_segment(a, b, min, max) {
+                                polyline(&mut self.canvas, &[a, b], color);
+                            }
+                        }
                     }
                 }
-                (Shape::Points(_), _) => {
-                    for (x, y, color) in points {
-                        self.canvas.set_colored(x, y, color);
+                Shape::Band(lower, upper) => {
+                    let edge = |points: &[Point<T, U>]| {
+                        let mut line: Vec<(f64, f64)> = points
+                            .iter()
+                            .map(|point| (point.x.to_f64(), point.y.to_f64()))
+                            .filter(|(x, y): &(f64, f64)| x.is_finite() && y.is_finite())
+                            .map(screen)
+                            .collect();
+                        line.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(cmp::Ordering::Equal));
+                        line
+                    };
+                    let (lower, upper) = (edge(lower), edge(upper));
+
+                    // outline both edges, and shade every other column in between
+                    for edge in [&lower, &upper] {
+                        for line in path::clip(edge, min, max) {
+                            polyline(&mut self.canvas, &line, color);
+                        }
+                    }
+                    for i in (0..=self.width).step_by(2) {
+                        let i = i as f64;
+                        if let (Some(a), Some(b)) =
+                            (path::interpolate(&lower, i), path::interpolate(&upper, i))
+                        {
+                            if let Some((a, b)) = path::clip_segment((i, a), (i, b), min, max) {
+                                polyline(&mut self.canvas, &[a, b], color);
+                            }
+                        }
                     }
                 }
-                (Shape::Steps(_), _) => {
-                    for pair in points.windows(2) {
-                        let (x1, y1, color) = pair[0];
-                        let (x2, y2, _) = pair[1];
+                Shape::Bars(_) if self.bar_layout != BarLayout::Overlap => {
+                    for bar in bars.next().unwrap_or_default() {
+                        let (left, top) = screen((bar.left, bar.top.max(bar.bottom)));
+                        let (right, bottom) = screen((bar.right, bar.top.min(bar.bottom)));
 
-                        self.canvas.line_colored(x1, y2, x2, y2, color);
-                        self.canvas.line_colored(x1, y1, x1, y2, color);
+                        // fill, leaving a column free between neighbour bars
+                        let mut i = left.round();
+                        let last = (right.round() - 1.0).max(i);
+                        while i <= last {
+                            if let Some((a, b)) =
+                                path::clip_segment((i, top), (i, bottom), min, max)
+                            {
+                                pen.polyline(&mut self.canvas, &[a, b], color);
+                            }
+                            i += 1.0;
+                        }
                     }
                 }
-                (Shape::Bars(_), _) => {
+                Shape::Bars(_) => {
+                    let points: Vec<_> = data
+                        .into_iter()
+                        .filter(|&(x, y)| {
+                            x.is_finite() && y.is_finite() && x >= self.xmin && x <= self.xmax
+                        })
+                        .map(|point| {
+                            let (i, j) = screen(point);
+                            (i.round(), j.round())
+                        })
+                        .collect();
+
                     for pair in points.windows(2) {
-                        let (x1, y1, color) = pair[0];
-                        let (x2, y2, _) = pair[1];
+                        let (x1, y1) = pair[0];
+                        let (x2, y2) = pair[1];
 
-                        self.canvas.line_colored(x1, y2, x2, y2, color);
-                        self.canvas.line_colored(x1, y1, x1, y2, color);
-                        self.canvas.line_colored(x1, self.height, x1, y1, color);
-                        self.canvas.line_colored(x2, self.height, x2, y2, color);
+                        for (a, b) in [
+                            ((x1, y2), (x2, y2)),
+                            ((x1, y1), (x1, y2)),
+                            ((x1, height), (x1, y1)),
+                            ((x2, height), (x2, y2)),
+                        ] {
+                            if let Some((a, b)) = path::clip_segment(a, b, min, max) {
+                                pen.polyline(&mut self.canvas, &[a, b], color);
+                            }
+                        }
                     }
                 }
             }
         }
     }
 
+    /// Puts the overlay characters into their canvas cells, as placeholders replaced by `rows`.
+    fn overlay(&mut self) {
+        for (k, &(i, j, _, _)) in self.overlay.iter().enumerate() {
+            if let Some(placeholder) = placeholder(k) {
+                self.canvas.set_char(i, j, placeholder);
+            }
+        }
+    }
+
+    /// Returns the rows of the canvas, with overlay characters in colour.
+    fn rows(&self) -> Vec<String> {
+        self.canvas
+            .rows()
+            .into_iter()
+            .map(|row| {
+                row.chars()
+                    .map(|c| {
+                        let k = (c as u32).wrapping_sub(PLACEHOLDERS.start) as usize;
+                        match self.overlay.get(k) {
+                            Some(&(_, _, glyph, color)) if PLACEHOLDERS.contains(&(c as u32)) => {
+                                glyph.to_string().color(color).to_string()
+                            }
+                            _ => c.to_string(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
     /// Return the frame.
     pub fn frame(&self) -> String {
-        self.canvas.frame()
+        self.rows().join("\n")
     }
 }
 
 impl<'a, T, U> Plot<'a, T, U> for Chart<'a, T, U>
 where
-    T: Into<f64> + From<f64> + Display + Clone,
-    U: Into<f64> + From<f64> + Display + Clone,
+    T: AxisValue,
+    U: AxisValue,
 {
     fn lineplot_with_tags(
         &'a mut self,
@@ -368,31 +1081,122 @@ where
         label: Option<String>,
         colour: PixelColor,
     ) -> &'a mut Chart<'a, T, U> {
-        self.shapes.push((shape, colour));
+        self.lineplot_with_style(shape, label, Style::new(colour))
+    }
+
+    fn lineplot_with_style(
+        &'a mut self,
+        shape: &'a Shape<Point<T, U>>,
+        label: Option<String>,
+        style: Style,
+    ) -> &'a mut Chart<'a, T, U> {
         if let Some(thing) = label {
-            self.labels.push((thing, colour));
+            self.labels.push((thing, style));
         }
-        // rescale ymin and ymax
-        let x_scale = Scale::new(self.xmin..self.xmax, 0.0..self.width as f64);
+
+        // sample continuous functions once, thin out long series, and rescale ymin and ymax
+        let (samples, bounds) = match shape {
+            Shape::Continuous(f) => {
+                sampling::adaptive(f, self.xmin, self.xmax, self.width, self.height)
+            }
+            Shape::Lines(dt) | Shape::Steps(dt) if dt.len() > self.width as usize => {
+                let data: Vec<_> = dt
+                    .iter()
+                    .map(|point| (point.x.to_f64(), point.y.to_f64()))
+                    .collect();
+                let decimated = match self.decimation {
+                    Decimation::MinMax => {
+                        sampling::decimate(&data, self.xmin, self.xmax, self.width)
+                    }
+                    Decimation::Lttb => {
+                        // run by run, since LTTB leaves out the points breaking lines,
+                        // with a share of the columns as large as their share of the points
+                        let mut picked = vec![];
+                        for run in path::split(&data, None) {
+                            let threshold = run.len() * (self.width as usize + 1) / data.len();
+                            if !picked.is_empty() {
+                                picked.push((f64::NAN, f64::NAN));
+                            }
+                            picked.extend(sampling::lttb(&run, threshold));
+                        }
+                        picked
+                    }
+                };
+                (decimated, None)
+            }
+            _ => (vec![], None),
+        };
 
         let ys: Vec<_> = match shape {
-            Shape::Continuous(f) => (0..self.width)
-                .filter_map(|i| {
-                    let x = x_scale.inv_linear(i as f64);
-                    let y = f(x);
-                    if y.is_normal() {
+            Shape::Continuous(_) => bounds.map(|(lo, hi)| vec![lo, hi]).unwrap_or_default(),
+            Shape::Boxes(boxes) => match boxes.orientation {
+                Orientation::Vertical => {
+                    self.x_categories = boxes.categories();
+                    boxes.bounds()
+                }
+                Orientation::Horizontal => {
+                    self.y_categories = boxes.categories();
+                    vec![-0.5, boxes.samples.len() as f64 - 0.5]
+                }
+            },
+            Shape::Candlesticks(candles) => candles
+                .bodies()
+                .into_iter()
+                .filter(|body| body.x >= self.xmin && body.x <= self.xmax)
+                .flat_map(|body| vec![body.low, body.high])
+                .collect(),
+            Shape::ErrorBars(measurements) => measurements
+                .iter()
+                .map(Measurement::values)
+                .filter(|((x, y), _)| *x >= self.xmin && *x <= self.xmax && y.is_finite())
+                .flat_map(|((_, y), (_, yerr))| vec![y - yerr, y + yerr])
+                .collect(),
+            Shape::Band(lower, upper) => lower
+                .iter()
+                .chain(upper.iter())
+                .filter_map(|point| {
+                    let x: f64 = point.x.to_f64();
+                    let y: f64 = point.y.to_f64();
+                    if x >= self.xmin && x <= self.xmax && y.is_finite() {
                         Some(y)
                     } else {
                         None
                     }
                 })
                 .collect(),
-            Shape::Points(dt) | Shape::Lines(dt) | Shape::Steps(dt) | Shape::Bars(dt) => dt
+            Shape::Lines(dt) | Shape::Steps(dt) => {
+                let data: Vec<_> = if samples.is_empty() {
+                    dt.iter()
+                        .map(|point| (point.x.to_f64(), point.y.to_f64()))
+                        .collect()
+                } else {
+                    samples.clone()
+                };
+                let mut ys: Vec<_> = data
+                    .iter()
+                    .filter(|&&(x, y)| x >= self.xmin && x <= self.xmax && y.is_finite())
+                    .map(|&(_, y)| y)
+                    .collect();
+
+                // lines coming from beyond the x-range are clipped where they cross its ends
+                for run in path::split(&data, self.gap) {
+                    let line = match shape {
+                        Shape::Steps(_) => path::steps(&run),
+                        _ => run,
+                    };
+                    for &x in &[self.xmin, self.xmax] {
+                        ys.extend(path::crossings(&line, x));
+                    }
+                }
+
+                ys
+            }
+            Shape::Points(dt) | Shape::Bars(dt) => dt
                 .iter()
                 .filter_map(|point| {
-                    let x: f64 = point.x.clone().into();
-                    let y: f64 = point.y.clone().into();
-                    if x >= self.xmin && x <= self.xmax {
+                    let x: f64 = point.x.to_f64();
+                    let y: f64 = point.y.to_f64();
+                    if x >= self.xmin && x <= self.xmax && y.is_finite() {
                         Some(y)
                     } else {
                         None
@@ -410,8 +1214,13 @@ where
             .min_by(|x, y| x.partial_cmp(y).unwrap_or(cmp::Ordering::Equal))
             .unwrap_or(&0.0);
 
-        self.ymin = f64::min(self.ymin, ymin);
-        self.ymax = f64::max(self.ymax, ymax);
+        self.shapes.push(Series {
+            shape,
+            style,
+            samples,
+            range: (ymin, ymax),
+        });
+        self.rescale();
 
         self
     }
@@ -420,3 +1229,330 @@ where
         self.lineplot_with_tags(shape, None, PixelColor::White)
     }
 }
+
+/// Private use characters standing for overlay characters in canvas rows.
+const PLACEHOLDERS: std::ops::Range<u32> = 0xE000..0xF900;
+
+/// Placeholder of the `k`-th overlay character, if there are enough of them.
+fn placeholder(k: usize) -> Option<char> {
+    let code = PLACEHOLDERS.start.checked_add(k as u32)?;
+    if PLACEHOLDERS.contains(&code) {
+        std::char::from_u32(code)
+    } else {
+        None
+    }
+}
+
+/// Draws lines in a line style, carrying its pattern over from one line to the next.
+struct Pen {
+    line: LineStyle,
+    /// Dots stepped over so far, the position within the pattern.
+    phase: usize,
+    /// Largest dot coordinates, thick lines don't grow beyond them.
+    bounds: (u32, u32),
+}
+
+impl Pen {
+    fn new(line: LineStyle, width: u32, height: u32) -> Self {
+        Pen {
+            line,
+            phase: 0,
+            bounds: (width, height),
+        }
+    }
+
+    /// Draws lines through consecutive points given in canvas coordinates.
+    fn polyline(&mut self, canvas: &mut BrailleCanvas, points: &[(f64, f64)], color: PixelColor) {
+        let points: Vec<_> = points
+            .iter()
+            .map(|&(x, y)| (x.round() as u32, y.round() as u32))
+            .collect();
+
+        for (k, pair) in points.windows(2).enumerate() {
+            // lines after the first start where the previous one ended
+            self.stroke(canvas, pair[0], pair[1], k > 0, color);
+        }
+        if let [(x, y)] = points[..] {
+            self.dot(canvas, (x, y), true, color);
+        }
+    }
+
+    /// Steps along a line dot by dot, like `BrailleCanvas::line_colored`.
+    fn stroke(
+        &mut self,
+        canvas: &mut BrailleCanvas,
+        (x1, y1): (u32, u32),
+        (x2, y2): (u32, u32),
+        joined: bool,
+        color: PixelColor,
+    ) {
+        let xdiff = cmp::max(x1, x2) - cmp::min(x1, x2);
+        let ydiff = cmp::max(y1, y2) - cmp::min(y1, y2);
+        let xdir = if x1 <= x2 { 1 } else { -1 };
+        let ydir = if y1 <= y2 { 1 } else { -1 };
+        let r = cmp::max(xdiff, ydiff);
+
+        for i in (if joined { 1 } else { 0 })..=r {
+            let mut x = x1 as i64;
+            let mut y = y1 as i64;
+            if ydiff != 0 {
+                y += ((i * ydiff) / r) as i64 * ydir;
+            }
+            if xdiff != 0 {
+                x += ((i * xdiff) / r) as i64 * xdir;
+            }
+
+            self.dot(canvas, (x as u32, y as u32), xdiff >= ydiff, color);
+        }
+    }
+
+    /// Draws the next dot of the pattern, if it is not a gap. Thick lines get a second dot
+    /// across the line, below `flat` lines and beside steep ones.
+    fn dot(
+        &mut self,
+        canvas: &mut BrailleCanvas,
+        (x, y): (u32, u32),
+        flat: bool,
+        color: PixelColor,
+    ) {
+        let pattern = self.line.pattern();
+        if pattern[self.phase % pattern.len()] {
+            canvas.set_colored(x, y, color);
+            if self.line == LineStyle::Thick {
+                let next = |v: u32, max: u32| if v < max { v + 1 } else { v.saturating_sub(1) };
+                if flat {
+                    canvas.set_colored(x, next(y, self.bounds.1), color);
+                } else {
+                    canvas.set_colored(next(x, self.bounds.0), y, color);
+                }
+            }
+        }
+        self.phase += 1;
+    }
+}
+
+/// Draws lines through consecutive points given in canvas coordinates.
+fn polyline(canvas: &mut BrailleCanvas, points: &[(f64, f64)], color: PixelColor) {
+    let points: Vec<_> = points
+        .iter()
+        .map(|&(x, y)| (x.round() as u32, y.round() as u32))
+        .collect();
+
+    for pair in points.windows(2) {
+        let (x1, y1) = pair[0];
+        let (x2, y2) = pair[1];
+
+        canvas.line_colored(x1, y1, x2, y2, color);
+    }
+    if let [(x, y)] = points[..] {
+        canvas.set_colored(x, y, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_series_are_drawn_on_the_bottom_row() {
+        let points = [Point { x: 0.0, y: 5.0 }, Point { x: 1.0, y: 5.0 }];
+        let line = Shape::Continuous(Box::new(|_| 5.0));
+
+        for shape in [Shape::Points(&points), line] {
+            let mut chart = Chart::<'_, f64, f64>::new(32, 32, 0.0, 1.0);
+            let plot = chart.lineplot(&shape);
+            plot.figures();
+            let frame = plot.frame();
+            let drawn: Vec<_> = frame
+                .lines()
+                .map(|row| row.chars().any(|c| c != '\u{2800}' && c != ' '))
+                .collect();
+
+            assert_eq!(Some(&true), drawn.last());
+            assert!(drawn[..drawn.len() - 1].iter().all(|&row| !row));
+        }
+    }
+
+    #[test]
+    fn lines_from_beyond_the_x_range_are_fitted_where_they_enter() {
+        let points: Vec<_> = [(-10.0, 0.0), (1.0, 11.0), (2.0, 11.0)]
+            .iter()
+            .map(|&(x, y)| Point { x, y })
+            .collect();
+
+        for shape in [Shape::Lines(&points), Shape::Steps(&points)] {
+            let mut chart = Chart::<'_, f64, f64>::new(32, 32, 0.0, 2.0);
+            let plot = chart.lineplot(&shape);
+
+            // the line enters at 10, the steps at 11
+            let entry = match shape {
+                Shape::Lines(_) => 10.0,
+                _ => 11.0,
+            };
+            assert_eq!((entry, 11.0), (plot.ymin, plot.ymax));
+        }
+    }
+
+    /// Draws the shapes and annotations of a chart, returns its rows without colours.
+    fn annotated(chart: &mut Chart<'_, f64, f64>) -> Vec<String> {
+        colored::control::set_override(false);
+        chart.figures();
+        let taken = chart.taken();
+        chart.annotations(&taken);
+        chart.overlay();
+
+        chart.rows()
+    }
+
+    /// Text of a row, without blank Braille characters and spaces.
+    fn text(row: &str) -> String {
+        row.chars()
+            .filter(|&c| c != '\u{2800}' && c != ' ')
+            .collect()
+    }
+
+    #[test]
+    fn hlines_beyond_the_y_range_are_not_drawn() {
+        for &y in &[200.0, -1.0, f64::NAN, f64::INFINITY] {
+            let mut chart = Chart::<'_, f64, f64>::new(32, 32, 0.0, 1.0);
+            chart.y_range(Some(0.0), Some(100.0)).annotate(
+                Annotation::HLine(y),
+                PixelColor::Red,
+                None,
+            );
+
+            let rows = annotated(&mut chart);
+            assert!(rows.iter().all(|row| text(row).is_empty()), "{}", y);
+        }
+
+        let mut chart = Chart::<'_, f64, f64>::new(32, 32, 0.0, 1.0);
+        chart.y_range(Some(0.0), Some(100.0)).annotate(
+            Annotation::HLine(50.0),
+            PixelColor::Red,
+            None,
+        );
+        let drawn: Vec<_> = annotated(&mut chart)
+            .iter()
+            .map(|row| !text(row).is_empty())
+            .collect();
+        assert_eq!(
+            vec![false, false, false, false, true, false, false, false, false],
+            drawn
+        );
+    }
+
+    #[test]
+    fn text_moves_to_the_nearest_free_row() {
+        let shape = Shape::Continuous(Box::new(|_| 50.0));
+        let mut chart = Chart::<'_, f64, f64>::new(32, 32, 0.0, 1.0);
+        let plot = chart
+            .y_range(Some(0.0), Some(100.0))
+            .annotate(
+                Annotation::Text(0.5, 50.0, "ab".into()),
+                PixelColor::Red,
+                None,
+            )
+            .annotate(
+                Annotation::Text(1.0, 0.0, "end".into()),
+                PixelColor::Red,
+                None,
+            )
+            .lineplot(&shape);
+
+        let rows = annotated(plot);
+        // the line keeps its row, the text goes to the row above
+        assert_eq!(17, rows[4].chars().count());
+        assert!(!rows[4].contains('a'));
+        assert_eq!(
+            Some(8),
+            rows[3].find("ab").map(|k| rows[3][..k].chars().count())
+        );
+        // text is moved left to fit before the last column
+        assert!(rows[8].ends_with("end"));
+    }
+
+    #[test]
+    fn text_wider_than_the_canvas_is_dropped() {
+        let mut chart = Chart::<'_, f64, f64>::new(32, 32, 0.0, 1.0);
+        chart.y_range(Some(0.0), Some(1.0)).annotate(
+            Annotation::Text(0.0, 0.5, "x".repeat(18)),
+            PixelColor::Red,
+            None,
+        );
+
+        let rows = annotated(&mut chart);
+        assert!(rows.iter().all(|row| text(row).is_empty()));
+    }
+
+    #[test]
+    fn lttb_decimation_keeps_breaks() {
+        let points: Vec<_> = (0..10_000)
+            .map(|i| {
+                let y = if i == 5000 {
+                    f64::NAN
+                } else {
+                    (i as f64).sin()
+                };
+                Point { x: i as f64, y }
+            })
+            .collect();
+        let shape = Shape::Lines(&points);
+
+        let mut chart = Chart::<'_, f64, f64>::new(100, 32, 0.0, 10_000.0);
+        let plot = chart.decimation(Decimation::Lttb).lineplot(&shape);
+        let samples = &plot.shapes[0].samples;
+
+        // half of the 101 columns for either run, and the break between them
+        assert_eq!(101, samples.len());
+        assert!(samples[50].1.is_nan());
+        assert_eq!((0.0, 9999.0), (samples[0].0, samples[100].0));
+    }
+
+    #[test]
+    fn labels_wider_than_half_the_canvas_are_displayed() {
+        let shape = Shape::Continuous(Box::new(|x| x * 1e9));
+        let wide = |value: f64| format!("{:.3} units", value);
+        for &(xmin, xmax) in &[(-12_345.678, 98_765.432), (0.0, 1e15)] {
+            let mut chart = Chart::<'_, f64, f64>::new(32, 32, xmin, xmax);
+            chart
+                .x_label_format(&wide)
+                .y_label_format(&wide)
+                .lineplot(&shape)
+                .display();
+        }
+
+        // integers are labeled in full, with 17 and 16 characters
+        let points = [
+            Point {
+                x: -1_000_000_000_000_000_i64,
+                y: 0.0,
+            },
+            Point {
+                x: 1_000_000_000_000_000,
+                y: 1.0,
+            },
+        ];
+        let shape = Shape::Lines(&points);
+        Chart::<'_, i64, f64>::new(32, 32, -1e15, 1e15)
+            .lineplot(&shape)
+            .display();
+    }
+
+    #[test]
+    fn bars_beyond_a_fixed_range_are_clipped() {
+        let points: Vec<_> = [(0.0, -50.0), (1.0, 5.0), (2.0, 8.0), (3.0, 50.0)]
+            .iter()
+            .map(|&(x, y)| Point { x, y })
+            .collect();
+        let shape = Shape::Bars(&points);
+
+        let mut chart = Chart::<'_, f64, f64>::new(32, 32, 0.0, 3.0);
+        let plot = chart.y_range(Some(0.0), Some(10.0)).lineplot(&shape);
+        plot.figures();
+        let frame = plot.frame();
+
+        // a 32 dots high canvas takes 9 rows, the 9th holding its last dot row
+        assert_eq!(9, frame.lines().count());
+        assert!(frame.lines().all(|row| row.chars().count() == 17));
+    }
+}
@@ -1,46 +1,294 @@
-use pointplots::{Chart, Plot, Shape};
+use meval::{Context, Expr};
+use pointplots::{terminal, Chart, PixelColor, Plot, Point, Shape};
 use std::process::exit;
+use structopt::clap::{Error, ErrorKind};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
 struct Opt {
     /// Formula to plot
     #[structopt(name = "FORMULA")]
-    formula: String,
+    formula: Option<String>,
+    /// Chart spec file (TOML or JSON) to display instead of a formula.
+    #[cfg(feature = "serde")]
+    #[structopt(long, parse(from_os_str), conflicts_with = "FORMULA")]
+    spec: Option<std::path::PathBuf>,
     /// X-axis start value.
     #[structopt(long, default_value = "-10.0")]
     xmin: f64,
     /// X-axis end value.
     #[structopt(long, default_value = "10.0")]
     xmax: f64,
-    /// Canvas width in points.
-    #[structopt(short, long, default_value = "180")]
-    width: u32,
-    /// Canvas height in points.
-    #[structopt(short, long, default_value = "60")]
-    height: u32,
+    /// Canvas width in points (fits the terminal by default).
+    #[structopt(short, long)]
+    width: Option<u32>,
+    /// Canvas height in points (fits the terminal by default).
+    #[structopt(short, long)]
+    height: Option<u32>,
+    /// Binds a constant used in the formula, e.g. `--var a=2`.
+    #[structopt(long = "var", number_of_values = 1, parse(try_from_str = parse_var))]
+    vars: Vec<(String, f64)>,
+    /// Defines a helper function used in the formula, e.g. `--func 'sq(t)=t^2'`.
+    #[structopt(long = "func", number_of_values = 1, parse(try_from_str = parse_func))]
+    funcs: Vec<Func>,
+    /// Plots the formula for several values of a constant, e.g. `--sweep a=1..5:5`.
+    #[structopt(long, parse(try_from_str = parse_sweep))]
+    sweep: Option<Sweep>,
+}
+
+/// User-defined function `name(args) = body`.
+struct Func {
+    name: String,
+    args: Vec<String>,
+    body: Expr,
+}
+
+/// Range of values taken by a constant, one plotted series per value.
+struct Sweep {
+    name: String,
+    start: f64,
+    end: f64,
+    count: usize,
+}
+
+impl Sweep {
+    fn values(&self) -> Vec<f64> {
+        if self.count == 1 {
+            return vec![self.start];
+        }
+
+        let step = (self.end - self.start) / (self.count - 1) as f64;
+        (0..self.count)
+            .map(|i| self.start + step * i as f64)
+            .collect()
+    }
+}
+
+/// Shape of a series with its label.
+type Series<'a> = (Option<String>, Shape<'a, Point<f64, f64>>);
+
+/// Rows printed around the chart: the formula title and the shell prompt.
+const RESERVED_ROWS: u16 = 2;
+
+/// Colours given to the series of a sweep, in order.
+const PALETTE: [PixelColor; 6] = [
+    PixelColor::Red,
+    PixelColor::Green,
+    PixelColor::Blue,
+    PixelColor::Yellow,
+    PixelColor::Magenta,
+    PixelColor::Cyan,
+];
+
+fn parse_var(s: &str) -> Result<(String, f64), String> {
+    let (name, value) = s.split_once('=').ok_or("expected NAME=VALUE")?;
+    let name = name.trim();
+    if !is_identifier(name) {
+        return Err(format!("expected NAME=VALUE, got `{}`", s));
+    }
+    let value = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid value for `{}`: {}", name, value))?;
+
+    Ok((name.to_string(), value))
+}
+
+fn parse_func(s: &str) -> Result<Func, String> {
+    let invalid = || format!("expected NAME(ARGS)=BODY, got `{}`", s);
+    let (head, body) = s.split_once('=').ok_or_else(invalid)?;
+    let (name, args) = head
+        .trim()
+        .strip_suffix(')')
+        .and_then(|head| head.split_once('('))
+        .ok_or_else(invalid)?;
+    let name = name.trim();
+    let args: Vec<_> = args.split(',').map(|a| a.trim().to_string()).collect();
+    if !is_identifier(name) || !args.iter().all(|arg| is_identifier(arg)) {
+        return Err(invalid());
+    }
+    let body = body.parse::<Expr>().map_err(|err| err.to_string())?;
+
+    Ok(Func {
+        name: name.to_string(),
+        args,
+        body,
+    })
+}
+
+/// Whether `s` can name a function or an argument in a formula.
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => chars.all(|c| c.is_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+fn parse_sweep(s: &str) -> Result<Sweep, String> {
+    let invalid = || "expected NAME=START..END:COUNT".to_string();
+    let (name, range) = s.split_once('=').ok_or_else(invalid)?;
+    let (range, count) = range.split_once(':').ok_or_else(invalid)?;
+    let (start, end) = range.split_once("..").ok_or_else(invalid)?;
+    if !is_identifier(name.trim()) {
+        return Err(invalid());
+    }
+
+    let sweep = Sweep {
+        name: name.trim().to_string(),
+        start: start.trim().parse().map_err(|_| invalid())?,
+        end: end.trim().parse().map_err(|_| invalid())?,
+        count: count.trim().parse().map_err(|_| invalid())?,
+    };
+    if sweep.count == 0 {
+        return Err("sweep needs at least one value".to_string());
+    }
+
+    Ok(sweep)
+}
+
+/// Builds the evaluation context: built-in constants and functions, then user
+/// constants and the swept constant, then user functions (each one may call the
+/// ones defined before it). Fails when a function body uses a name that is neither
+/// one of its arguments nor defined by then.
+fn context(opt: &Opt, swept: Option<(&str, f64)>) -> Result<Context<'static>, meval::Error> {
+    let mut ctx = Context::new();
+    for (name, value) in &opt.vars {
+        ctx.var(name.as_str(), *value);
+    }
+    if let Some((name, value)) = swept {
+        ctx.var(name, value);
+    }
+
+    for func in &opt.funcs {
+        let scope = ctx.clone();
+        let names: Vec<_> = func.args.iter().map(String::as_str).collect();
+        // binding only checks the names used in the body, it is evaluated below
+        func.body
+            .clone()
+            .bindn_with_context(&scope, &names)
+            .map(drop)?;
+
+        let args = func.args.clone();
+        let body = func.body.clone();
+        ctx.funcn(
+            func.name.as_str(),
+            move |values: &[f64]| {
+                let bound: Vec<_> = args.iter().cloned().zip(values.iter().cloned()).collect();
+                body.eval_with_context((bound, &scope)).unwrap_or(f64::NAN)
+            },
+            func.args.len(),
+        );
+    }
+
+    Ok(ctx)
+}
+
+/// Plots the shapes with their labels, in the colours of the palette for a sweep.
+fn plot<'a>(
+    opt: &Opt,
+    chart: &'a mut Chart<'a, f64, f64>,
+    shapes: &'a [Series<'a>],
+) -> &'a mut Chart<'a, f64, f64> {
+    let mut plot = chart;
+    for (i, (label, shape)) in shapes.iter().enumerate() {
+        let colour = match opt.sweep {
+            Some(_) => PALETTE[i % PALETTE.len()],
+            None => PixelColor::White,
+        };
+        plot = plot.lineplot_with_tags(shape, label.clone(), colour);
+    }
+
+    plot
+}
+
+#[cfg(feature = "serde")]
+fn display_spec(opt: &Opt, path: &std::path::Path) {
+    let result = pointplots::spec::ChartSpec::from_path(path).and_then(|mut spec| {
+        spec.width = opt.width.or(spec.width);
+        spec.height = opt.height.or(spec.height);
+        spec.display()
+    });
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        exit(1);
+    }
 }
 
 fn main() {
     let opt = Opt::from_args();
 
-    let res = opt
-        .formula
-        .parse()
-        .and_then(|expr: meval::Expr| expr.bind("x"));
-    let func = match res {
-        Ok(func) => func,
-        Err(err) => {
-            // if there was an error with parsing
-            // or binding "x", exit with error
+    #[cfg(feature = "serde")]
+    {
+        if let Some(path) = &opt.spec {
+            display_spec(&opt, path);
+            return;
+        }
+    }
 
+    let formula = match &opt.formula {
+        Some(formula) => formula,
+        None => Error::with_description(
+            "The following required arguments were not provided:\n    <FORMULA>",
+            ErrorKind::MissingRequiredArgument,
+        )
+        .exit(),
+    };
+    let expr: Expr = match formula.parse() {
+        Ok(expr) => expr,
+        Err(err) => {
             eprintln!("{}", err);
             exit(1);
         }
     };
 
-    println!("y = {}", opt.formula);
-    Chart::<'_, f64, f64>::new(opt.width, opt.height, opt.xmin, opt.xmax)
-        .lineplot(&Shape::Continuous(Box::new(|x| func(x.into()) as f64)))
-        .display();
+    // a single series for the plain formula, or one per value of the sweep
+    let series: Vec<(Option<String>, Result<Context, meval::Error>)> = match &opt.sweep {
+        None => vec![(None, context(&opt, None))],
+        Some(sweep) => sweep
+            .values()
+            .into_iter()
+            .map(|value| {
+                let ctx = context(&opt, Some((sweep.name.as_str(), value)));
+                (Some(format!("{} = {}", sweep.name, value)), ctx)
+            })
+            .collect(),
+    };
+
+    let mut shapes = vec![];
+    for (label, ctx) in series {
+        match ctx.and_then(|ctx| expr.clone().bind_with_context(ctx, "x")) {
+            Ok(func) => shapes.push((label, Shape::Continuous(Box::new(func)))),
+            Err(err) => {
+                // if there was an error with binding "x"
+                // or any other variable, exit with error
+
+                eprintln!("{}", err);
+                exit(1);
+            }
+        }
+    }
+
+    let legend_rows = if opt.sweep.is_some() {
+        shapes.len() as u16 + 1
+    } else {
+        0
+    };
+    let (width, height) = terminal::chart_size(
+        (opt.width, opt.height),
+        (opt.xmin, opt.xmax),
+        (None, None),
+        &shapes.iter().map(|(_, shape)| shape).collect::<Vec<_>>(),
+        RESERVED_ROWS + legend_rows,
+    );
+
+    println!("y = {}", formula);
+    let mut chart = Chart::new(width, height, opt.xmin, opt.xmax);
+    let plot = plot(&opt, &mut chart, &shapes);
+
+    plot.display();
+    if opt.sweep.is_some() {
+        plot.legends();
+    }
 }
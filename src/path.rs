@@ -0,0 +1,264 @@
+//! Polylines through data points.
+
+/// Splits points into runs drawn as connected lines. A run ends at a point with a
+/// non-finite coordinate (which is dropped), or between consecutive points further
+/// apart than `gap` along the x-axis.
+pub(crate) fn split(points: &[(f64, f64)], gap: Option<f64>) -> Vec<Vec<(f64, f64)>> {
+    let mut runs = vec![];
+    let mut run: Vec<(f64, f64)> = vec![];
+
+    for &(x, y) in points {
+        if !x.is_finite() || !y.is_finite() {
+            runs.push(std::mem::take(&mut run));
+            continue;
+        }
+        if let (Some(gap), Some(&(last, _))) = (gap, run.last()) {
+            if (x - last).abs() > gap {
+                runs.push(std::mem::take(&mut run));
+            }
+        }
+        run.push((x, y));
+    }
+    runs.push(run);
+
+    runs.retain(|run| !run.is_empty());
+    runs
+}
+
+/// Points of a staircase through a run of points: rising at each point, then running flat
+/// until the next one.
+pub(crate) fn steps(run: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut steps: Vec<_> = run.first().cloned().into_iter().collect();
+    for pair in run.windows(2) {
+        let ((x1, _), (x2, y2)) = (pair[0], pair[1]);
+        steps.push((x1, y2));
+        steps.push((x2, y2));
+    }
+
+    steps
+}
+
+/// Clips a polyline to the rectangle spanned by corners `min` and `max`, cutting segments
+/// that cross an edge at the edge (Liang–Barsky). Parts of the line that leave the rectangle
+/// and come back are returned separately.
+pub(crate) fn clip(line: &[(f64, f64)], min: (f64, f64), max: (f64, f64)) -> Vec<Vec<(f64, f64)>> {
+    let mut parts = vec![];
+    let mut part = vec![];
+
+    if let [point] = line {
+        if clip_segment(*point, *point, min, max).is_some() {
+            parts.push(vec![*point]);
+        }
+        return parts;
+    }
+
+    for pair in line.windows(2) {
+        match clip_segment(pair[0], pair[1], min, max) {
+            Some((start, end)) => {
+                if part.last() != Some(&start) {
+                    if !part.is_empty() {
+                        parts.push(std::mem::take(&mut part));
+                    }
+                    part.push(start);
+                }
+                part.push(end);
+            }
+            None => {
+                if !part.is_empty() {
+                    parts.push(std::mem::take(&mut part));
+                }
+            }
+        }
+    }
+    if !part.is_empty() {
+        parts.push(part);
+    }
+
+    parts
+}
+
+/// Clips the segment `a`-`b` to the rectangle spanned by `min` and `max`, returns `None`
+/// when the segment lies outside.
+pub(crate) fn clip_segment(
+    a: (f64, f64),
+    b: (f64, f64),
+    min: (f64, f64),
+    max: (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+
+    // for each edge: p is the direction towards it, q the distance inside it
+    for &(p, q) in &[
+        (-dx, a.0 - min.0),
+        (dx, max.0 - a.0),
+        (-dy, a.1 - min.1),
+        (dy, max.1 - a.1),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+
+    let at = |t: f64| (a.0 + t * dx, a.1 + t * dy);
+    let start = if t0 == 0.0 { a } else { at(t0) };
+    let end = if t1 == 1.0 { b } else { at(t1) };
+
+    Some((start, end))
+}
+
+/// Value of a polyline sorted along the x-axis at `x`, interpolated linearly between the
+/// surrounding points. Returns `None` outside of the polyline.
+pub(crate) fn interpolate(line: &[(f64, f64)], x: f64) -> Option<f64> {
+    if let [(x0, y0)] = line {
+        return if *x0 == x { Some(*y0) } else { None };
+    }
+
+    line.windows(2).find_map(|pair| {
+        let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+        if x < x1 || x > x2 {
+            None
+        } else if x2 == x1 {
+            Some(y1)
+        } else {
+            Some(y1 + (y2 - y1) * (x - x1) / (x2 - x1))
+        }
+    })
+}
+
+/// Values of a polyline where it crosses the vertical line at `x`, interpolated linearly
+/// between the points on either side, whichever way the polyline runs.
+pub(crate) fn crossings(line: &[(f64, f64)], x: f64) -> Vec<f64> {
+    line.windows(2)
+        .filter_map(|pair| {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            if x < x1.min(x2) || x > x1.max(x2) {
+                None
+            } else if x2 == x1 {
+                Some(y1)
+            } else {
+                Some(y1 + (y2 - y1) * (x - x1) / (x2 - x1))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN: (f64, f64) = (0.0, 0.0);
+    const MAX: (f64, f64) = (10.0, 10.0);
+
+    #[test]
+    fn split_ends_runs_at_non_finite_points() {
+        let points = [
+            (0.0, 1.0),
+            (1.0, f64::NAN),
+            (2.0, 3.0),
+            (3.0, 4.0),
+            (f64::INFINITY, 0.0),
+        ];
+
+        assert_eq!(
+            vec![vec![(0.0, 1.0)], vec![(2.0, 3.0), (3.0, 4.0)]],
+            split(&points, None)
+        );
+    }
+
+    #[test]
+    fn split_ends_runs_at_gaps_wider_than_the_threshold() {
+        let points = [(0.0, 1.0), (1.0, 1.0), (3.0, 1.0), (6.0, 1.0)];
+
+        assert_eq!(1, split(&points, None).len());
+        assert_eq!(1, split(&points, Some(3.0)).len());
+        assert_eq!(
+            vec![vec![(0.0, 1.0), (1.0, 1.0), (3.0, 1.0)], vec![(6.0, 1.0)]],
+            split(&points, Some(2.0))
+        );
+        assert_eq!(3, split(&points, Some(1.0)).len());
+    }
+
+    #[test]
+    fn interpolate_between_surrounding_points() {
+        let line = [(0.0, 0.0), (2.0, 4.0), (4.0, 0.0)];
+
+        assert_eq!(Some(2.0), interpolate(&line, 1.0));
+        assert_eq!(Some(4.0), interpolate(&line, 2.0));
+        assert_eq!(Some(1.0), interpolate(&line, 3.5));
+        assert_eq!(None, interpolate(&line, -0.5));
+        assert_eq!(None, interpolate(&line, 4.5));
+        assert_eq!(Some(7.0), interpolate(&[(1.0, 7.0)], 1.0));
+        assert_eq!(None, interpolate(&[(1.0, 7.0)], 2.0));
+    }
+
+    #[test]
+    fn crossings_in_either_direction() {
+        let line = [(-2.0, 0.0), (2.0, 4.0), (-2.0, 8.0)];
+
+        assert_eq!(vec![2.0, 6.0], crossings(&line, 0.0));
+        assert!(crossings(&line, 3.0).is_empty());
+    }
+
+    #[test]
+    fn clip_segment_cuts_at_every_edge() {
+        // from the middle through the left, right, top and bottom edges
+        let crossings = [
+            ((-10.0, 5.0), (0.0, 5.0)),
+            ((20.0, 5.0), (10.0, 5.0)),
+            ((5.0, -5.0), (5.0, 0.0)),
+            ((5.0, 15.0), (5.0, 10.0)),
+            ((-5.0, 15.0), (0.0, 10.0)),
+        ];
+        for &(outside, edge) in &crossings {
+            let middle = (5.0, 5.0);
+            assert_eq!(
+                Some((middle, edge)),
+                clip_segment(middle, outside, MIN, MAX)
+            );
+            assert_eq!(
+                Some((edge, middle)),
+                clip_segment(outside, middle, MIN, MAX)
+            );
+        }
+    }
+
+    #[test]
+    fn clip_segment_drops_segments_outside() {
+        assert_eq!(None, clip_segment((-5.0, 2.0), (-1.0, 8.0), MIN, MAX));
+        assert_eq!(None, clip_segment((-5.0, 4.0), (4.0, -5.0), MIN, MAX));
+        assert_eq!(None, clip_segment((2.0, 11.0), (8.0, 11.0), MIN, MAX));
+    }
+
+    #[test]
+    fn clip_splits_lines_leaving_and_coming_back() {
+        let line = [(0.0, 5.0), (4.0, 5.0), (5.0, 20.0), (6.0, 5.0), (10.0, 5.0)];
+        let parts = clip(&line, MIN, MAX);
+
+        assert_eq!(2, parts.len());
+        assert_eq!(&[(0.0, 5.0), (4.0, 5.0)], &parts[0][..2]);
+        assert_eq!(&[(6.0, 5.0), (10.0, 5.0)], &parts[1][parts[1].len() - 2..]);
+        // both parts end on the top edge
+        assert_eq!(10.0, parts[0].last().unwrap().1);
+        assert_eq!(10.0, parts[1][0].1);
+    }
+
+    #[test]
+    fn clip_keeps_single_points_inside() {
+        assert_eq!(vec![vec![(3.0, 4.0)]], clip(&[(3.0, 4.0)], MIN, MAX));
+        assert!(clip(&[(3.0, 14.0)], MIN, MAX).is_empty());
+        assert!(clip(&[], MIN, MAX).is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+//! Adaptive sampling of real value functions, and decimation of long series.
+
+use std::cmp::Ordering;
+
+/// Depth of bisection below one canvas column.
+const MAX_DEPTH: u32 = 6;
+
+/// Jump (in tolerances) above which a segment that does not shrink is considered broken.
+const JUMP: f64 = 4.0;
+
+/// Smallest and largest of some values.
+type Bounds = (f64, f64);
+
+/// Samples `f` over `[xmin, xmax]` for a canvas `columns` points wide and `rows` points high,
+/// returning the samples along with the smallest and largest finite values at the columns.
+///
+/// Every column is sampled, and intervals where the function is steep, curved, or leaves its
+/// domain are bisected further. Poles and jumps are reported as a sample with `NaN` value,
+/// as is every point where the function itself is not finite, so that the curve is broken
+/// there instead of connected across. Samples between columns can get arbitrarily close to
+/// a pole, so only those at the columns bound the values, like they did before refining.
+pub(crate) fn adaptive(
+    f: &dyn Fn(f64) -> f64,
+    xmin: f64,
+    xmax: f64,
+    columns: u32,
+    rows: u32,
+) -> (Vec<(f64, f64)>, Option<Bounds>) {
+    let step = (xmax - xmin) / columns as f64;
+    let uniform: Vec<_> = (0..=columns)
+        .map(|i| {
+            let x = xmin + step * i as f64;
+            (x, finite(f(x)))
+        })
+        .collect();
+
+    // one point of tolerance on the vertical axis, as seen on the first pass
+    let (lo, hi) = uniform
+        .iter()
+        .filter(|(_, y)| y.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, y)| {
+            (lo.min(y), hi.max(y))
+        });
+    let bounds = if lo <= hi { Some((lo, hi)) } else { None };
+    let tolerance = if hi > lo {
+        (hi - lo) / rows as f64
+    } else {
+        1.0
+    };
+
+    let mut samples = Vec::with_capacity(uniform.len() * 2);
+    for pair in uniform.windows(2) {
+        samples.push(pair[0]);
+        refine(f, pair[0], pair[1], tolerance, 0, &mut samples);
+    }
+    samples.extend(uniform.last());
+
+    (samples, bounds)
+}
+
+/// Bisects `(a, b)`, pushing the samples found strictly between them.
+fn refine(
+    f: &dyn Fn(f64) -> f64,
+    (xa, ya): (f64, f64),
+    (xb, yb): (f64, f64),
+    tolerance: f64,
+    depth: u32,
+    samples: &mut Vec<(f64, f64)>,
+) {
+    let xm = (xa + xb) / 2.0;
+    let ym = finite(f(xm));
+
+    if depth == MAX_DEPTH {
+        if ya.is_finite() && yb.is_finite() && broken(ya, ym, yb, tolerance) {
+            samples.push((xm, f64::NAN));
+        } else {
+            samples.push((xm, ym));
+        }
+        return;
+    }
+
+    let split = match (ya.is_finite(), ym.is_finite(), yb.is_finite()) {
+        (true, true, true) => {
+            // always look between columns once, narrow spikes hide there
+            depth == 0 || (yb - ya).abs() > tolerance || (ym - (ya + yb) / 2.0).abs() > tolerance
+        }
+        (false, false, false) => false,
+        // the interval crosses an edge of the domain
+        _ => true,
+    };
+
+    if split {
+        refine(f, (xa, ya), (xm, ym), tolerance, depth + 1, samples);
+        samples.push((xm, ym));
+        refine(f, (xm, ym), (xb, yb), tolerance, depth + 1, samples);
+    }
+}
+
+/// Tells if the function jumps on the smallest interval instead of moving continuously:
+/// either the middle value escapes the values at the ends (a pole), or the whole jump
+/// happens on one side of the middle (a step).
+fn broken(ya: f64, ym: f64, yb: f64, tolerance: f64) -> bool {
+    let (lo, hi) = (ya.min(yb), ya.max(yb));
+    if !ym.is_finite() {
+        return true;
+    }
+    if hi - lo <= JUMP * tolerance {
+        return false;
+    }
+
+    ym < lo - tolerance
+        || ym > hi + tolerance
+        || (ym - ya).abs() < tolerance
+        || (ym - yb).abs() < tolerance
+}
+
+/// Replaces infinities with `NaN`, so only finite values are plotted.
+fn finite(y: f64) -> f64 {
+    if y.is_finite() {
+        y
+    } else {
+        f64::NAN
+    }
+}
+
+/// Keeps the first, lowest, highest and last point of every run of consecutive points
+/// nearest to the same of `columns + 1` columns of dots over `[xmin, xmax]`, so that lines
+/// through the remaining points cover the same dots as lines through all of them.
+/// Points with a non-finite coordinate are kept, as they break lines.
+pub(crate) fn decimate(data: &[(f64, f64)], xmin: f64, xmax: f64, columns: u32) -> Vec<(f64, f64)> {
+    let width = (xmax - xmin) / columns as f64;
+    if width <= 0.0 || !width.is_finite() {
+        return data.to_vec();
+    }
+    // points off either side of the canvas make one column each, which only their ends reach
+    let column = |x: f64| match x {
+        x if x < xmin => i64::MIN,
+        x if x > xmax => i64::MAX,
+        x => ((x - xmin) / width).round() as i64,
+    };
+
+    // column of the current run, with indices of its first, lowest, highest and last points
+    let mut run: Option<(i64, [usize; 4])> = None;
+    let mut decimated = vec![];
+    for (i, &(x, y)) in data.iter().enumerate() {
+        if !x.is_finite() || !y.is_finite() {
+            flush(data, run.take(), &mut decimated);
+            decimated.push((x, y));
+            continue;
+        }
+
+        match &mut run {
+            Some((c, [_, lowest, highest, last])) if *c == column(x) => {
+                if y < data[*lowest].1 {
+                    *lowest = i;
+                }
+                if y > data[*highest].1 {
+                    *highest = i;
+                }
+                *last = i;
+            }
+            _ => {
+                flush(data, run.take(), &mut decimated);
+                run = Some((column(x), [i; 4]));
+            }
+        }
+    }
+    flush(data, run, &mut decimated);
+
+    decimated
+}
+
+/// Pushes the points of a run of [`decimate`] in their original order.
+fn flush(data: &[(f64, f64)], run: Option<(i64, [usize; 4])>, decimated: &mut Vec<(f64, f64)>) {
+    if let Some((_, mut indices)) = run {
+        indices.sort_unstable();
+        let mut indices = indices.to_vec();
+        indices.dedup();
+        decimated.extend(indices.into_iter().map(|i| data[i]));
+    }
+}
+
+/// Picks `threshold` of the points with finite coordinates by Largest-Triangle-Three-Buckets:
+/// the first and last points, and from each of the buckets between them the point forming
+/// the largest triangle with the point picked before and the average of the next bucket.
+/// A `threshold` below 3 is raised to 3, so that there is a bucket between the ends.
+/// Points are expected in order along the x-axis.
+pub(crate) fn lttb(data: &[(f64, f64)], threshold: usize) -> Vec<(f64, f64)> {
+    let data: Vec<_> = data
+        .iter()
+        .cloned()
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect();
+    let threshold = threshold.max(3);
+    if threshold >= data.len() {
+        return data;
+    }
+
+    let every = (data.len() - 2) as f64 / (threshold - 2) as f64;
+    let bucket = |i: usize| {
+        let start = (i as f64 * every) as usize + 1;
+        let end = ((i + 1) as f64 * every) as usize + 1;
+        start..end.min(data.len() - 1)
+    };
+
+    let mut sampled = vec![data[0]];
+    let mut picked = data[0];
+    for i in 0..threshold - 2 {
+        // the last bucket is followed by the last point alone
+        let next = if i + 3 == threshold {
+            &data[data.len() - 1..]
+        } else {
+            &data[bucket(i + 1)]
+        };
+        let (sx, sy) = next
+            .iter()
+            .fold((0.0, 0.0), |(sx, sy), &(x, y)| (sx + x, sy + y));
+        let (cx, cy) = (sx / next.len() as f64, sy / next.len() as f64);
+
+        let (ax, ay) = picked;
+        let area = |&(x, y): &(f64, f64)| ((ax - cx) * (y - ay) - (ax - x) * (cy - ay)).abs();
+        picked = data[bucket(i)]
+            .iter()
+            .cloned()
+            .max_by(|a, b| area(a).partial_cmp(&area(b)).unwrap_or(Ordering::Equal))
+            .unwrap_or(picked);
+        sampled.push(picked);
+    }
+    sampled.push(data[data.len() - 1]);
+
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Consecutive finite samples, the segments a curve is drawn with.
+    fn segments(samples: &[(f64, f64)]) -> Vec<((f64, f64), (f64, f64))> {
+        samples
+            .windows(2)
+            .filter(|pair| pair[0].1.is_finite() && pair[1].1.is_finite())
+            .map(|pair| (pair[0], pair[1]))
+            .collect()
+    }
+
+    #[test]
+    fn adaptive_breaks_reciprocal_at_zero() {
+        let (samples, bounds) = adaptive(&|x| 1.0 / x, -10.0, 10.0, 120, 60);
+
+        // the nearest columns are a sixth away from the pole
+        assert_eq!(
+            Some((-6.0, 6.0)),
+            bounds.map(|(lo, hi)| (lo.round(), hi.round()))
+        );
+        assert!(samples
+            .iter()
+            .any(|&(x, y)| x.abs() < 0.01 && y.abs() > 100.0));
+        assert!(segments(&samples)
+            .iter()
+            .all(|&((_, a), (_, b))| a.signum() == b.signum()));
+    }
+
+    #[test]
+    fn adaptive_breaks_tangent_at_poles() {
+        let (samples, bounds) = adaptive(&f64::tan, -10.0, 10.0, 120, 60);
+
+        // bounded by the columns alone, not by the refined samples next to the poles
+        let columns: Vec<_> = (0..=120)
+            .map(|i| (-10.0 + 20.0 / 120.0 * i as f64).tan())
+            .collect();
+        let lo = columns.iter().cloned().fold(f64::INFINITY, f64::min);
+        let hi = columns.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!(Some((lo, hi)), bounds);
+        assert!(samples.iter().any(|(_, y)| y.abs() > 10.0 * hi));
+        // tangent only decreases across a pole
+        assert!(segments(&samples).iter().all(|&((_, a), (_, b))| a < b));
+        let breaks = samples.iter().filter(|(_, y)| y.is_nan()).count();
+        assert!(breaks >= 6, "{} breaks", breaks);
+    }
+
+    #[test]
+    fn adaptive_without_values_has_no_bounds() {
+        let (samples, bounds) = adaptive(&|x| x.ln(), -10.0, -1.0, 40, 40);
+
+        assert_eq!(None, bounds);
+        assert!(samples.iter().all(|(_, y)| y.is_nan()));
+    }
+}
@@ -20,6 +20,22 @@ impl Scale {
         r.max(self.range.start).min(self.range.end)
     }
 
+    /// Translates value from domain to range scale, without limiting it to the range.
+    /// An empty domain maps to the start of the range, like it does with `linear`.
+    /// ```
+    /// # use pointplots::scale::Scale;
+    /// assert_eq!(-1.4, Scale::new(0_f64..10_f64, -1_f64..1_f64).extrapolate(-2.0));
+    /// assert_eq!(-1.0, Scale::new(5_f64..5_f64, -1_f64..1_f64).extrapolate(5.0));
+    /// ```
+    pub fn extrapolate(&self, x: f64) -> f64 {
+        if self.domain.start == self.domain.end {
+            return self.range.start;
+        }
+
+        let p = (x - self.domain.start) / (self.domain.end - self.domain.start);
+        self.range.start + p * (self.range.end - self.range.start)
+    }
+
     /// Translates value from range to domain scale.
     /// ```
     /// # use pointplots::scale::Scale;
@@ -35,3 +51,44 @@ impl Scale {
         Scale { domain, range }
     }
 }
+
+/// Round step between about `count` ticks spanning `min..max`: 1, 2 or 5 times a power of ten.
+///
+/// ```
+/// # use pointplots::scale::step;
+/// assert_eq!(2.0, step(0.0, 10.0, 5));
+/// assert_eq!(0.05, step(0.0, 0.3, 5));
+/// ```
+pub fn step(min: f64, max: f64, count: usize) -> f64 {
+    let raw = (max - min).abs() / count.max(1) as f64;
+    if !raw.is_normal() {
+        return 1.0;
+    }
+    let magnitude = 10_f64.powf(raw.log10().floor());
+    let nice = match raw / magnitude {
+        f if f < 1.5 => 1.0,
+        f if f < 3.0 => 2.0,
+        f if f < 7.0 => 5.0,
+        _ => 10.0,
+    };
+
+    nice * magnitude
+}
+
+/// Multiples of `step` within `min..=max`.
+///
+/// ```
+/// # use pointplots::scale::ticks;
+/// assert_eq!(vec![-4.0, -2.0, 0.0, 2.0], ticks(-5.0, 3.0, 2.0));
+/// ```
+pub fn ticks(min: f64, max: f64, step: f64) -> Vec<f64> {
+    if step <= 0.0 || !step.is_finite() || !min.is_finite() || !max.is_finite() {
+        return vec![];
+    }
+    // tolerate rounding errors at the ends
+    let epsilon = step * 1e-9;
+    let first = ((min - epsilon) / step).ceil() as i64;
+    let last = ((max + epsilon) / step).floor() as i64;
+
+    (first..=last).map(|k| k as f64 * step).collect()
+}
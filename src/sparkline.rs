@@ -0,0 +1,158 @@
+//! One-line sparklines for embedding into status lines and tables.
+//!
+//! ```
+//! use pointplots::sparkline::Sparkline;
+//!
+//! let latency = [12.0, 14.0, 13.0, 18.0, 25.0, 16.0, 12.0];
+//! println!("latency {} {}ms", Sparkline::new(&latency), latency[latency.len() - 1]);
+//! ```
+
+use crate::scale::Scale;
+use crate::PixelColor;
+use colored::*;
+use std::fmt;
+
+/// Bars of increasing height.
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Braille dots of the left and right columns, from the bottom row up.
+const DOTS: [[u8; 4]; 2] = [[0x40, 0x04, 0x02, 0x01], [0x80, 0x20, 0x10, 0x08]];
+
+/// Characters sparklines are made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyphs {
+    /// One block bar per value, eight levels high.
+    Blocks,
+    /// One Braille dot per value, two values per character and four levels high.
+    Braille,
+}
+
+/// Sparkline of a series of values.
+#[derive(Debug, Clone)]
+pub struct Sparkline<'a> {
+    values: &'a [f64],
+    min: Option<f64>,
+    max: Option<f64>,
+    glyphs: Glyphs,
+    highlight: Option<PixelColor>,
+}
+
+impl<'a> Sparkline<'a> {
+    /// Creates a sparkline of block bars scaled between the smallest and the largest value.
+    pub fn new(values: &'a [f64]) -> Self {
+        Sparkline {
+            values,
+            min: None,
+            max: None,
+            glyphs: Glyphs::Blocks,
+            highlight: None,
+        }
+    }
+
+    /// Fixes the value drawn as the lowest level, smaller values are clamped.
+    ///
+    /// ```
+    /// # use pointplots::sparkline::Sparkline;
+    /// assert_eq!("▁▁▁", Sparkline::new(&[1.0, 2.0, 3.0]).min(10.0).render());
+    /// ```
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Fixes the value drawn as the highest level, larger values are clamped.
+    ///
+    /// ```
+    /// # use pointplots::sparkline::Sparkline;
+    /// assert_eq!("███", Sparkline::new(&[1.0, 2.0, 3.0]).max(0.0).render());
+    /// ```
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the characters the sparkline is made of.
+    pub fn glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Colours the character showing the last value.
+    pub fn highlight_last(mut self, color: PixelColor) -> Self {
+        self.highlight = Some(color);
+        self
+    }
+
+    /// Level of every value from 0 to `levels - 1`, `None` for values that are not finite.
+    /// Constant values are drawn at the middle level, unless a bound is fixed beyond them.
+    fn levels(&self, levels: usize) -> Vec<Option<usize>> {
+        let finite = self.values.iter().cloned().filter(|v| v.is_finite());
+        let min = self
+            .min
+            .unwrap_or_else(|| finite.clone().fold(f64::INFINITY, f64::min));
+        let max = self
+            .max
+            .unwrap_or_else(|| finite.fold(f64::NEG_INFINITY, f64::max));
+        let scale = Scale::new(min..max, 0.0..(levels - 1) as f64);
+
+        self.values
+            .iter()
+            .map(|&v| {
+                if !v.is_finite() {
+                    None
+                } else if max > min {
+                    Some(scale.linear(v).round() as usize)
+                } else if let Some(min) = self.min {
+                    Some(if v <= min { 0 } else { levels - 1 })
+                } else if let Some(max) = self.max {
+                    Some(if v >= max { levels - 1 } else { 0 })
+                } else {
+                    Some(levels / 2)
+                }
+            })
+            .collect()
+    }
+
+    /// Renders the sparkline.
+    ///
+    /// ```
+    /// # use pointplots::sparkline::{Glyphs, Sparkline};
+    /// assert_eq!("▁█▄ ▁", Sparkline::new(&[0.0, 7.0, 3.0, f64::NAN, -1.0]).min(0.0).render());
+    /// assert_eq!("⡈⠂", Sparkline::new(&[0.0, 3.0, 2.0]).glyphs(Glyphs::Braille).render());
+    /// ```
+    pub fn render(&self) -> String {
+        let mut cells: Vec<String> = match self.glyphs {
+            Glyphs::Blocks => self
+                .levels(BARS.len())
+                .into_iter()
+                .map(|level| level.map_or(' ', |level| BARS[level]).to_string())
+                .collect(),
+            Glyphs::Braille => self
+                .levels(DOTS[0].len())
+                .chunks(2)
+                .map(|pair| {
+                    let bits = pair
+                        .iter()
+                        .zip(DOTS.iter())
+                        .filter_map(|(level, column)| level.map(|level| column[level]))
+                        .fold(0, |bits, dot| bits | dot);
+                    std::char::from_u32(0x2800 + bits as u32)
+                        .unwrap_or(' ')
+                        .to_string()
+                })
+                .collect(),
+        };
+
+        if let (Some(color), Some(last)) = (self.highlight, cells.last_mut()) {
+            *last = last.color(color).to_string();
+        }
+
+        cells.concat()
+    }
+}
+
+impl fmt::Display for Sparkline<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
@@ -0,0 +1,385 @@
+//! Declarative chart definitions, read from TOML or JSON.
+//!
+//! A spec describes the same things that are otherwise set up in code: canvas size,
+//! axis ranges, and a list of series, each one a formula or a column of a data file.
+//!
+//! ```toml
+//! title = "Latency"
+//! width = 120
+//! height = 60
+//! xmin = 0.0
+//! xmax = 100.0
+//! ymin = 0.0
+//!
+//! [vars]
+//! slo = 250.0
+//!
+//! [[series]]
+//! file = "latency.csv"
+//! x_column = "hour"
+//! column = "p99"
+//! shape = "lines"
+//! color = "red"
+//! label = "p99"
+//!
+//! [[series]]
+//! formula = "slo"
+//! color = "yellow"
+//! label = "SLO"
+//! ```
+
+use crate::{terminal, Chart, PixelColor, Plot, Point, Shape};
+use meval::{Context, Expr};
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Shape of a series with its label and colour.
+type Tagged<'a> = (Shape<'a, Point<f64, f64>>, Option<String>, PixelColor);
+
+/// Chart definition.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChartSpec {
+    /// Title printed above the chart.
+    pub title: Option<String>,
+    /// Canvas width in points, fitted to the terminal when omitted.
+    pub width: Option<u32>,
+    /// Canvas height in points, fitted to the terminal when omitted.
+    pub height: Option<u32>,
+    /// X-axis start value.
+    pub xmin: f64,
+    /// X-axis end value.
+    pub xmax: f64,
+    /// Y-axis start value, fitted to the series when omitted.
+    pub ymin: Option<f64>,
+    /// Y-axis end value, fitted to the series when omitted.
+    pub ymax: Option<f64>,
+    /// Draws borders around the canvas.
+    #[serde(default)]
+    pub borders: bool,
+    /// Constants available to every formula.
+    #[serde(default)]
+    pub vars: BTreeMap<String, f64>,
+    /// Plotted series, drawn in order.
+    pub series: Vec<SeriesSpec>,
+}
+
+/// Single plotted series: either a `formula` of `x`, or a `column` of a data `file`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeriesSpec {
+    /// Formula of `x`, e.g. `"sin(x) / x"`.
+    pub formula: Option<String>,
+    /// Data file with whitespace or comma separated columns, relative to the spec file.
+    pub file: Option<PathBuf>,
+    /// Column holding x values (row number when omitted).
+    pub x_column: Option<Column>,
+    /// Column holding y values.
+    pub column: Option<Column>,
+    /// How points of a data file are drawn (formulas are always continuous).
+    #[serde(default)]
+    pub shape: ShapeKind,
+    /// Colour name, as understood by `colored` ("red", "bright blue", ...).
+    pub color: Option<String>,
+    /// Legend entry.
+    pub label: Option<String>,
+}
+
+/// Column of a data file, by zero-based index or by header name.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+/// Kinds of [`Shape`](../enum.Shape.html) a data series can be drawn as.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShapeKind {
+    Points,
+    #[default]
+    Lines,
+    Steps,
+    Bars,
+}
+
+/// Errors produced while reading a spec or the data it refers to.
+#[derive(Debug)]
+pub enum SpecError {
+    /// The spec or a data file could not be read.
+    Io(PathBuf, std::io::Error),
+    /// The spec is not valid TOML or JSON, or does not describe a chart.
+    Syntax(String),
+    /// A formula could not be parsed or refers to unknown variables.
+    Formula(String, meval::Error),
+    /// A series is incomplete or its data file is malformed.
+    Series(String),
+    /// The canvas is too small, or an axis range is empty.
+    Chart(String),
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpecError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            SpecError::Syntax(err) => write!(f, "invalid spec: {}", err),
+            SpecError::Formula(formula, err) => write!(f, "{}: {}", formula, err),
+            SpecError::Series(err) | SpecError::Chart(err) => f.write_str(err),
+        }
+    }
+}
+
+impl Error for SpecError {}
+
+impl ChartSpec {
+    /// Parses a spec in TOML format.
+    ///
+    /// ```
+    /// # use pointplots::spec::ChartSpec;
+    /// let spec = ChartSpec::from_toml("xmin = -5.0\nxmax = 5.0\n[[series]]\nformula = \"x^2\"").unwrap();
+    /// assert_eq!(1, spec.series.len());
+    /// ```
+    pub fn from_toml(source: &str) -> Result<Self, SpecError> {
+        toml::from_str(source).map_err(|err| SpecError::Syntax(err.to_string()))
+    }
+
+    /// Parses a spec in JSON format.
+    pub fn from_json(source: &str) -> Result<Self, SpecError> {
+        serde_json::from_str(source).map_err(|err| SpecError::Syntax(err.to_string()))
+    }
+
+    /// Reads a spec file, in JSON if its extension is `.json` and in TOML otherwise.
+    ///
+    /// Data files of the series are resolved relative to the spec file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, SpecError> {
+        let path = path.as_ref();
+        let source =
+            fs::read_to_string(path).map_err(|err| SpecError::Io(path.to_path_buf(), err))?;
+        let mut spec = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&source)?,
+            _ => Self::from_toml(&source)?,
+        };
+
+        if let Some(dir) = path.parent() {
+            for series in &mut spec.series {
+                if let Some(file) = &series.file {
+                    series.file = Some(dir.join(file));
+                }
+            }
+        }
+
+        Ok(spec)
+    }
+
+    /// Loads the data, draws the chart and prints it along with the title and legend.
+    ///
+    /// ```
+    /// # use pointplots::spec::ChartSpec;
+    /// let spec = ChartSpec::from_toml("width = 10\nxmin = 0.0\nxmax = 1.0\nseries = []").unwrap();
+    /// assert_eq!(
+    ///     "width should be at least 32, 10 is provided",
+    ///     spec.display().unwrap_err().to_string()
+    /// );
+    /// ```
+    pub fn display(&self) -> Result<(), SpecError> {
+        self.check(
+            self.width.unwrap_or(terminal::DEFAULT_SIZE.0),
+            self.height.unwrap_or(terminal::DEFAULT_SIZE.1),
+        )?;
+
+        let mut ctx = Context::new();
+        for (name, value) in &self.vars {
+            ctx.var(name.as_str(), *value);
+        }
+
+        // load every data file first, shapes borrow their points
+        let data = self
+            .series
+            .iter()
+            .map(|series| match &series.file {
+                Some(file) => load(file, series).map(Some),
+                None => Ok(None),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut shapes = vec![];
+        for (series, points) in self.series.iter().zip(&data) {
+            let shape = match (&series.formula, points) {
+                (Some(formula), None) => {
+                    let func = formula
+                        .parse::<Expr>()
+                        .and_then(|expr| expr.bind_with_context(ctx.clone(), "x"))
+                        .map_err(|err| SpecError::Formula(formula.clone(), err))?;
+                    Shape::Continuous(Box::new(func))
+                }
+                (None, Some(points)) => match series.shape {
+                    ShapeKind::Points => Shape::Points(points),
+                    ShapeKind::Lines => Shape::Lines(points),
+                    ShapeKind::Steps => Shape::Steps(points),
+                    ShapeKind::Bars => Shape::Bars(points),
+                },
+                _ => {
+                    return Err(SpecError::Series(
+                        "every series needs either a formula or a file".to_string(),
+                    ))
+                }
+            };
+            let colour = match &series.color {
+                Some(name) => name
+                    .parse::<PixelColor>()
+                    .map_err(|_| SpecError::Series(format!("unknown colour `{}`", name)))?,
+                None => PixelColor::White,
+            };
+            shapes.push((shape, series.label.clone(), colour));
+        }
+
+        let labelled = shapes
+            .iter()
+            .filter(|(_, label, _)| label.is_some())
+            .count();
+        let legend_rows = if labelled > 0 { labelled as u16 + 1 } else { 0 };
+        // besides the title and the legend, a row is kept for the shell prompt
+        let reserved_rows = self.title.is_some() as u16 + legend_rows + 1;
+        let (width, height) = terminal::chart_size(
+            (self.width, self.height),
+            (self.xmin, self.xmax),
+            (self.ymin, self.ymax),
+            &shapes.iter().map(|(shape, _, _)| shape).collect::<Vec<_>>(),
+            reserved_rows,
+        );
+
+        if let Some(title) = &self.title {
+            println!("{}", title);
+        }
+
+        let mut chart = Chart::new(width, height, self.xmin, self.xmax);
+        let plot = self.plot(&mut chart, &shapes);
+
+        if self.borders {
+            plot.borders();
+        }
+        plot.display();
+        if self.series.iter().any(|series| series.label.is_some()) {
+            plot.legends();
+        }
+
+        Ok(())
+    }
+
+    /// Plots the shapes with their labels and colours within the y range of the spec.
+    fn plot<'a>(
+        &self,
+        chart: &'a mut Chart<'a, f64, f64>,
+        shapes: &'a [Tagged<'a>],
+    ) -> &'a mut Chart<'a, f64, f64> {
+        let mut plot = chart.y_range(self.ymin, self.ymax);
+        for (shape, label, colour) in shapes {
+            plot = plot.lineplot_with_tags(shape, label.clone(), *colour);
+        }
+
+        plot
+    }
+
+    /// Checks what `Chart` would otherwise panic on or draw nothing with.
+    fn check(&self, width: u32, height: u32) -> Result<(), SpecError> {
+        for (name, size) in [("width", width), ("height", height)] {
+            if size < 32 {
+                return Err(SpecError::Chart(format!(
+                    "{} should be at least 32, {} is provided",
+                    name, size
+                )));
+            }
+        }
+
+        let ranges = [
+            ("x", Some(self.xmin), Some(self.xmax)),
+            ("y", self.ymin, self.ymax),
+        ];
+        for (axis, min, max) in ranges {
+            if let (Some(min), Some(max)) = (min, max) {
+                if min.partial_cmp(&max) != Some(Ordering::Less) {
+                    return Err(SpecError::Chart(format!(
+                        "{}min should be less than {}max, got {} and {}",
+                        axis, axis, min, max
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads points of a series from a whitespace or comma separated data file.
+///
+/// Empty lines and lines starting with `#` are skipped; the first line is a header
+/// when any of its fields is not a number.
+fn load(path: &Path, series: &SeriesSpec) -> Result<Vec<Point<f64, f64>>, SpecError> {
+    let source = fs::read_to_string(path).map_err(|err| SpecError::Io(path.to_path_buf(), err))?;
+    let mut rows = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|field| !field.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .peekable();
+
+    let header = match rows.peek() {
+        Some(first) if first.iter().any(|field| field.parse::<f64>().is_err()) => rows.next(),
+        _ => None,
+    };
+    let index = |column: &Column| match column {
+        Column::Index(i) => Ok(*i),
+        Column::Name(name) => header
+            .as_ref()
+            .and_then(|header| header.iter().position(|field| field == name))
+            .ok_or_else(|| SpecError::Series(format!("{}: no column `{}`", path.display(), name))),
+    };
+
+    let y_column = match &series.column {
+        Some(column) => index(column)?,
+        None => {
+            return Err(SpecError::Series(format!(
+                "{}: series needs a column",
+                path.display()
+            )))
+        }
+    };
+    let x_column = series.x_column.as_ref().map(index).transpose()?;
+
+    rows.enumerate()
+        .map(|(i, fields)| {
+            let value = |column: usize| {
+                fields
+                    .get(column)
+                    .and_then(|field| field.parse::<f64>().ok())
+                    .ok_or_else(|| {
+                        SpecError::Series(format!(
+                            "{}: no number in column {} of row {}",
+                            path.display(),
+                            column,
+                            i + 1
+                        ))
+                    })
+            };
+            let x = match x_column {
+                Some(column) => value(column)?,
+                None => i as f64,
+            };
+
+            Ok(Point {
+                x,
+                y: value(y_column)?,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,176 @@
+//! Drawing attributes of a shape, beyond its colour.
+//!
+//! ```
+//! use pointplots::style::{LineStyle, Marker, Style};
+//! use pointplots::{Chart, PixelColor, Plot, Point, Shape};
+//!
+//! let a: Vec<_> = (0..20).map(|i| Point { x: i as f64, y: (i as f64 * 0.5).sin() }).collect();
+//! let b: Vec<_> = (0..20).map(|i| Point { x: i as f64, y: (i as f64 * 0.5).cos() }).collect();
+//!
+//! Chart::<'_, f64, f64>::new(120, 60, 0.0, 20.0)
+//!     .lineplot_with_style(&Shape::Points(&a), None, Style::new(PixelColor::Red).marker(Marker::Cross))
+//!     .lineplot_with_style(&Shape::Points(&b), None, Style::new(PixelColor::Blue).marker(Marker::Char('o')))
+//!     .lineplot_with_style(&Shape::Lines(&b), None, Style::new(PixelColor::Blue).line(LineStyle::Dashed))
+//!     .display();
+//! ```
+
+use crate::PixelColor;
+use std::cmp;
+
+/// Symbol drawn at every point of a scatter plot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    /// A single dot.
+    Dot,
+    /// Diagonal cross.
+    Cross,
+    /// Upright cross.
+    Plus,
+    /// Ring around the point.
+    Circle,
+    /// Square outline around the point.
+    Square,
+    /// Any character, always drawn as text over the canvas cell.
+    Char(char),
+}
+
+/// Distance from the point to the edges of markers, in dots.
+const RADIUS: i32 = 2;
+
+impl Marker {
+    /// Offsets of the Braille dots making up the marker, from the point.
+    pub(crate) fn dots(&self) -> Vec<(i32, i32)> {
+        let r = RADIUS;
+        let around = (-r..=r).flat_map(|dx| (-r..=r).map(move |dy| (dx, dy)));
+        match self {
+            Marker::Dot | Marker::Char(_) => vec![(0, 0)],
+            Marker::Cross => around.filter(|(dx, dy)| dx.abs() == dy.abs()).collect(),
+            Marker::Plus => around.filter(|(dx, dy)| *dx == 0 || *dy == 0).collect(),
+            Marker::Circle => around
+                .filter(|(dx, dy)| {
+                    let d = dx * dx + dy * dy;
+                    d > 2 && d <= 5
+                })
+                .collect(),
+            Marker::Square => around
+                .filter(|(dx, dy)| dx.abs() == r || dy.abs() == r)
+                .collect(),
+        }
+    }
+
+    /// Character standing for the marker when drawn as text.
+    ///
+    /// ```
+    /// # use pointplots::style::Marker;
+    /// assert_eq!('×', Marker::Cross.glyph());
+    /// assert_eq!('#', Marker::Char('#').glyph());
+    /// ```
+    pub fn glyph(&self) -> char {
+        match self {
+            Marker::Dot => '•',
+            Marker::Cross => '×',
+            Marker::Plus => '+',
+            Marker::Circle => '○',
+            Marker::Square => '□',
+            Marker::Char(c) => *c,
+        }
+    }
+}
+
+/// Pattern of the lines of a shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineStyle {
+    /// Continuous line.
+    Solid,
+    /// Dashes with short gaps.
+    Dashed,
+    /// Single dots with gaps.
+    Dotted,
+    /// Dashes and single dots alternately.
+    DashDot,
+    /// Continuous line two dots wide.
+    Thick,
+}
+
+impl LineStyle {
+    /// Dots drawn (`true`) and skipped (`false`) along a line, repeated.
+    pub(crate) fn pattern(&self) -> &'static [bool] {
+        match self {
+            LineStyle::Solid | LineStyle::Thick => &[true],
+            LineStyle::Dashed => &[true, true, true, false, false],
+            LineStyle::Dotted => &[true, false, false],
+            LineStyle::DashDot => &[true, true, true, true, false, false, true, false, false],
+        }
+    }
+
+    /// Short horizontal line in this style, for legends.
+    ///
+    /// ```
+    /// # use pointplots::style::LineStyle;
+    /// assert_eq!("⠉⠉⠉", LineStyle::Solid.swatch());
+    /// assert_eq!("⠉⠁⠈", LineStyle::Dashed.swatch());
+    /// ```
+    pub fn swatch(&self) -> String {
+        let pattern = self.pattern();
+        let cells = cmp::max(3, pattern.len().div_ceil(2));
+        let (left, right) = match self {
+            LineStyle::Thick => (0x03, 0x18),
+            _ => (0x01, 0x08),
+        };
+
+        (0..cells)
+            .map(|cell| {
+                let dot = |i: usize| pattern[i % pattern.len()];
+                let bits = if dot(2 * cell) { left } else { 0 }
+                    | if dot(2 * cell + 1) { right } else { 0 };
+                std::char::from_u32(0x2800 + bits).unwrap_or(' ')
+            })
+            .collect()
+    }
+}
+
+/// Colour and other drawing attributes of a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub(crate) colour: PixelColor,
+    pub(crate) marker: Marker,
+    pub(crate) line: LineStyle,
+    /// Draw markers as characters over the canvas cells instead of Braille dots.
+    pub(crate) text_markers: bool,
+}
+
+impl Style {
+    /// Creates a style drawing in `colour`, with solid lines and single dots as markers.
+    pub fn new(colour: PixelColor) -> Self {
+        Style {
+            colour,
+            marker: Marker::Dot,
+            line: LineStyle::Solid,
+            text_markers: false,
+        }
+    }
+
+    /// Sets the marker drawn at every point of a scatter plot.
+    pub fn marker(mut self, marker: Marker) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Sets the pattern of lines, steps, bars and continuous shapes.
+    pub fn line(mut self, line: LineStyle) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Draws markers as characters over the canvas cells, taking a whole cell each,
+    /// instead of Braille dots.
+    pub fn text_markers(mut self) -> Self {
+        self.text_markers = true;
+        self
+    }
+
+    /// Tells if markers are drawn as text.
+    pub(crate) fn marks_text(&self) -> bool {
+        self.text_markers || matches!(self.marker, Marker::Char(_))
+    }
+}
@@ -0,0 +1,514 @@
+//! Charts as standalone SVG images, drawn with vector lines instead of Braille dots.
+
+use crate::annotation::Annotation;
+use crate::bars::BarLayout;
+use crate::scale::Scale;
+use crate::style::{LineStyle, Marker, Style};
+use crate::{errorbar, layout, path, AxisValue, Chart, PixelColor, Shape};
+use std::cmp::Ordering;
+
+/// Pixels per canvas dot.
+const SCALE: f64 = 4.0;
+/// Space around the chart, in pixels.
+const PAD: f64 = 12.0;
+/// Font size of the labels, in pixels.
+const FONT: f64 = 12.0;
+/// Width of a character of the monospaced font, in pixels.
+const CHAR: f64 = FONT * 0.6;
+/// Height of a line of text, in pixels.
+const LINE: f64 = FONT * 1.5;
+/// Distance from a point to the edges of its marker, in pixels.
+const MARKER: f64 = 6.0;
+/// Opacity of shaded areas.
+const SHADE: f64 = 0.25;
+const BACKGROUND: &str = "#1e1e1e";
+const FOREGROUND: &str = "#d0d0d0";
+
+/// Renders the chart, after arranging its bars like the terminal output does.
+pub(crate) fn render<T, U>(chart: &Chart<'_, T, U>) -> String
+where
+    T: AxisValue,
+    U: AxisValue,
+{
+    let mut bars = chart.arrange_bars().into_iter();
+    let (width, height) = (chart.width as f64, chart.height as f64);
+    let x_scale = Scale::new(chart.xmin..chart.xmax, 0.0..width);
+    let y_scale = Scale::new(chart.ymin..chart.ymax, 0.0..height);
+
+    // chart coordinates into pixels
+    let at = |(x, y): (f64, f64)| {
+        (
+            PAD + x_scale.extrapolate(x) * SCALE,
+            PAD + (height - y_scale.extrapolate(y)) * SCALE,
+        )
+    };
+    let (left, top, right, bottom) = (PAD, PAD, PAD + width * SCALE, PAD + height * SCALE);
+
+    // grid lines and axes beneath everything else
+    let mut plot = vec![];
+    if let Some(grid) = chart.grid {
+        let colour = css(grid.colour);
+        if grid.vertical && chart.xmin < chart.xmax {
+            let (major, minor) = grid.lines(chart.xmin, chart.xmax);
+            for (xs, spacing) in [(major, grid.pattern.0), (minor, grid.pattern.1)] {
+                for x in xs {
+                    let x = at((x, 0.0)).0;
+                    plot.extend(line((x, top), (x, bottom), &colour, &dots(spacing)));
+                }
+            }
+        }
+        if grid.horizontal && chart.ymin < chart.ymax {
+            let (major, minor) = grid.lines(chart.ymin, chart.ymax);
+            for (ys, spacing) in [(major, grid.pattern.0), (minor, grid.pattern.1)] {
+                for y in ys {
+                    let y = at((0.0, y)).1;
+                    plot.extend(line((left, y), (right, y), &colour, &dots(spacing)));
+                }
+            }
+        }
+    }
+    if chart.xmin <= 0.0 && chart.xmax >= 0.0 {
+        let x = at((0.0, 0.0)).0;
+        plot.extend(line((x, top), (x, bottom), FOREGROUND, &dots(3)));
+    }
+    if chart.ymin <= 0.0 && chart.ymax >= 0.0 {
+        let y = at((0.0, 0.0)).1;
+        plot.extend(line((left, y), (right, y), FOREGROUND, &dots(3)));
+    }
+
+    for (annotation, colour) in &chart.annotations {
+        let colour = css(*colour);
+        let dashed = stroke(LineStyle::Dashed);
+        match annotation {
+            Annotation::HLine(y) => {
+                let y = at((0.0, y.to_f64())).1;
+                plot.extend(line((left, y), (right, y), &colour, &dashed));
+            }
+            Annotation::VLine(x) => {
+                let x = at((x.to_f64(), 0.0)).0;
+                plot.extend(line((x, top), (x, bottom), &colour, &dashed));
+            }
+            Annotation::XRange(from, to) => {
+                let (from, to) = (at((from.to_f64(), 0.0)).0, at((to.to_f64(), 0.0)).0);
+                plot.extend(rect((from, top), (to, bottom), &colour, true));
+            }
+            Annotation::YRange(from, to) => {
+                let (from, to) = (at((0.0, from.to_f64())).1, at((0.0, to.to_f64())).1);
+                plot.extend(rect((left, from), (right, to), &colour, true));
+            }
+            Annotation::Text(x, y, label) => {
+                plot.extend(text(at((x.to_f64(), y.to_f64())), "start", &colour, label));
+            }
+        }
+    }
+
+    for series in &chart.shapes {
+        let colour = css(series.style.colour);
+        let style = stroke(series.style.line);
+        let solid = stroke(LineStyle::Solid);
+        let data = series.data();
+        let pixels = |run: Vec<(f64, f64)>| run.into_iter().map(at).collect::<Vec<_>>();
+
+        match series.shape {
+            Shape::Continuous(_) => {
+                for run in path::split(&data, None) {
+                    plot.extend(polyline(&pixels(run), &colour, &style));
+                }
+            }
+            Shape::Lines(_) => {
+                for run in path::split(&data, chart.gap) {
+                    plot.extend(polyline(&pixels(run), &colour, &style));
+                }
+            }
+            Shape::Steps(_) => {
+                for run in path::split(&data, chart.gap) {
+                    plot.extend(polyline(&pixels(path::steps(&run)), &colour, &style));
+                }
+            }
+            Shape::Points(_) => {
+                for point in data {
+                    if point.0.is_finite() && point.1.is_finite() {
+                        plot.extend(marker(at(point), &series.style, &colour));
+                    }
+                }
+            }
+            Shape::Boxes(boxes) => {
+                let (segments, outliers) = boxes.figures();
+                for (a, b) in segments {
+                    plot.extend(line(at(a), at(b), &colour, &solid));
+                }
+                for point in outliers {
+                    plot.extend(circle(at(point), MARKER / 3.0, &colour, true));
+                }
+            }
+            Shape::Candlesticks(candles) => {
+                for body in candles.bodies() {
+                    let (colour, rising) = if body.rising() {
+                        (css(candles.up), true)
+                    } else {
+                        (css(candles.down), false)
+                    };
+                    let wick = (at((body.x, body.low)), at((body.x, body.high)));
+                    plot.extend(line(wick.0, wick.1, &colour, &solid));
+                    let corner = at((body.x - body.half_width, body.open.max(body.close)));
+                    let opposite = at((body.x + body.half_width, body.open.min(body.close)));
+                    let fill = if rising { "none" } else { &colour };
+                    plot.extend(finite(&[corner, opposite]).map(|_| {
+                        format!(
+                            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}" stroke="{}"/>"#,
+                            corner.0,
+                            corner.1,
+                            opposite.0 - corner.0,
+                            opposite.1 - corner.1,
+                            fill,
+                            colour
+                        )
+                    }));
+                }
+            }
+            Shape::ErrorBars(measurements) => {
+                let cap = errorbar::CAP * SCALE;
+                for measurement in measurements.iter() {
+                    let ((x, y), (xerr, yerr)) = measurement.values();
+                    if !x.is_finite() || !y.is_finite() {
+                        continue;
+                    }
+
+                    let (i, j) = at((x, y));
+                    plot.extend(circle((i, j), MARKER / 3.0, &colour, true));
+                    if yerr > 0.0 {
+                        let (top, bottom) = (at((x, y + yerr)).1, at((x, y - yerr)).1);
+                        plot.extend(line((i, top), (i, bottom), &colour, &solid));
+                        for end in [top, bottom] {
+                            plot.extend(line((i - cap, end), (i + cap, end), &colour, &solid));
+                        }
+                    }
+                    if xerr > 0.0 {
+                        let (left, right) = (at((x - xerr, y)).0, at((x + xerr, y)).0);
+                        plot.extend(line((left, j), (right, j), &colour, &solid));
+                        for end in [left, right] {
+                            plot.extend(line((end, j - cap), (end, j + cap), &colour, &solid));
+                        }
+                    }
+                }
+            }
+            Shape::Band(lower, upper) => {
+                let edge = |points: &[crate::Point<T, U>]| {
+                    let mut line: Vec<_> = points
+                        .iter()
+                        .map(|point| (point.x.to_f64(), point.y.to_f64()))
+                        .filter(|(x, y)| x.is_finite() && y.is_finite())
+                        .map(at)
+                        .collect();
+                    line.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+                    line
+                };
+                let (lower, upper) = (edge(lower), edge(upper));
+
+                // the region between the edges, then both edges
+                let outline: Vec<_> = upper.iter().chain(lower.iter().rev()).cloned().collect();
+                plot.push(format!(
+                    r#"<polygon points="{}" fill="{}" fill-opacity="{}"/>"#,
+                    coordinates(&outline),
+                    colour,
+                    SHADE
+                ));
+                for edge in [&lower, &upper] {
+                    plot.extend(polyline(edge, &colour, &solid));
+                }
+            }
+            Shape::Bars(_) if chart.bar_layout != BarLayout::Overlap => {
+                // leave a dot free between neighbour bars, as on the canvas
+                for bar in bars.next().unwrap_or_default() {
+                    let (left, top) = at((bar.left, bar.top.max(bar.bottom)));
+                    let (right, bottom) = at((bar.right, bar.top.min(bar.bottom)));
+                    let right = (right - SCALE).max(left);
+                    plot.extend(rect((left, top), (right, bottom), &colour, false));
+                }
+            }
+            Shape::Bars(_) => {
+                let points: Vec<_> = data
+                    .into_iter()
+                    .filter(|&(x, y)| {
+                        x.is_finite() && y.is_finite() && x >= chart.xmin && x <= chart.xmax
+                    })
+                    .map(at)
+                    .collect();
+
+                for pair in points.windows(2) {
+                    let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+                    let outline = [(x1, bottom), (x1, y1), (x1, y2), (x2, y2), (x2, bottom)];
+                    plot.extend(polyline(&outline, &colour, &style));
+                }
+            }
+        }
+    }
+
+    // labels of the y-axis to the right of the plot, aligned like in the terminal
+    let mut labels = vec![];
+    let mut margin = 0;
+    if chart.y_categories.is_empty() {
+        let (ymax, ymin) = (chart.y_label(chart.ymax), chart.y_label(chart.ymin));
+        margin = layout::width(&ymax).max(layout::width(&ymin));
+        let end = right + CHAR * (margin + 1) as f64;
+        labels.extend(text((end, top), "end", FOREGROUND, &ymax));
+        labels.extend(text((end, bottom), "end", FOREGROUND, &ymin));
+    } else {
+        for (y, name) in &chart.y_categories {
+            margin = margin.max(layout::width(name));
+            let y = at((0.0, *y)).1;
+            labels.extend(text((right + CHAR, y), "start", FOREGROUND, name));
+        }
+    }
+
+    // labels of the x-axis below the plot, the upper one after the lower when both don't fit
+    let baseline = bottom + LINE;
+    let mut extent = right + CHAR * (margin + 1) as f64;
+    if chart.x_categories.is_empty() {
+        let (xmin, xmax) = (chart.x_label(chart.xmin), chart.x_label(chart.xmax));
+        let (wmin, wmax) = (layout::width(&xmin) as f64, layout::width(&xmax) as f64);
+        labels.extend(text((left, baseline), "start", FOREGROUND, &xmin));
+        if (wmin + wmax + 1.0) * CHAR <= right - left {
+            labels.extend(text((right, baseline), "end", FOREGROUND, &xmax));
+        } else {
+            let start = left + (wmin + 1.0) * CHAR;
+            labels.extend(text((start, baseline), "start", FOREGROUND, &xmax));
+            extent = extent.max(start + wmax * CHAR);
+        }
+    } else {
+        for (x, name) in &chart.x_categories {
+            labels.extend(text(
+                (at((*x, 0.0)).0, baseline),
+                "middle",
+                FOREGROUND,
+                name,
+            ));
+        }
+    }
+
+    // legend rows, each with a sample of the line style like the terminal legend
+    let mut row = baseline;
+    for (label, style) in &chart.labels {
+        row += LINE;
+        let colour = css(style.colour);
+        let sample = (left, row);
+        labels.extend(line(
+            sample,
+            (sample.0 + 3.0 * CHAR, row),
+            &colour,
+            &stroke(style.line),
+        ));
+        let start = sample.0 + 4.0 * CHAR;
+        labels.extend(text((start, row), "start", &colour, label));
+        extent = extent.max(start + layout::width(label) as f64 * CHAR);
+    }
+
+    let (total_width, total_height) = (extent + PAD, row + LINE / 2.0 + PAD);
+    // named after the plot size, so the clip paths of several images in one page
+    // only share an id when they are the same
+    let clip = format!("pointplots-{}x{}", chart.width, chart.height);
+    let mut svg = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0:.0}" height="{1:.0}" viewBox="0 0 {0:.0} {1:.0}" font-family="monospace" font-size="{2}">"#,
+            total_width, total_height, FONT
+        ),
+        format!(
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            BACKGROUND
+        ),
+        format!(
+            r#"<clipPath id="{}"><rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}"/></clipPath>"#,
+            clip,
+            left,
+            top,
+            right - left,
+            bottom - top
+        ),
+        format!(r#"<g clip-path="url(#{})">"#, clip),
+    ];
+    svg.extend(plot);
+    svg.push("</g>".to_string());
+    svg.extend(labels);
+    svg.push("</svg>".to_string());
+
+    svg.join("\n")
+}
+
+/// Colour of a canvas dot as a CSS colour, as shown by common dark terminal themes.
+fn css(colour: PixelColor) -> String {
+    let hex = match colour {
+        PixelColor::Black => "#000000",
+        PixelColor::Red => "#cd3131",
+        PixelColor::Green => "#0dbc79",
+        PixelColor::Yellow => "#e5e510",
+        PixelColor::Blue => "#2472c8",
+        PixelColor::Magenta => "#bc3fbc",
+        PixelColor::Cyan => "#11a8cd",
+        PixelColor::White => "#e5e5e5",
+        PixelColor::BrightBlack => "#666666",
+        PixelColor::BrightRed => "#f14c4c",
+        PixelColor::BrightGreen => "#23d18b",
+        PixelColor::BrightYellow => "#f5f543",
+        PixelColor::BrightBlue => "#3b8eea",
+        PixelColor::BrightMagenta => "#d670d6",
+        PixelColor::BrightCyan => "#29b8db",
+        PixelColor::BrightWhite => "#ffffff",
+        PixelColor::TrueColor { r, g, b } => return format!("#{:02x}{:02x}{:02x}", r, g, b),
+    };
+
+    hex.to_string()
+}
+
+/// Stroke attributes of a line style.
+fn stroke(line: LineStyle) -> String {
+    let dashes = match line {
+        LineStyle::Solid | LineStyle::Thick => None,
+        LineStyle::Dashed => Some("12 8"),
+        LineStyle::Dotted => Some("3 9"),
+        LineStyle::DashDot => Some("16 8 3 8"),
+    };
+    let width = if line == LineStyle::Thick { 3.0 } else { 1.5 };
+
+    match dashes {
+        Some(dashes) => format!(r#" stroke-width="{}" stroke-dasharray="{}""#, width, dashes),
+        None => format!(r#" stroke-width="{}""#, width),
+    }
+}
+
+/// Stroke attributes of a line of single dots `spacing` canvas dots apart.
+fn dots(spacing: u32) -> String {
+    if spacing <= 1 {
+        r#" stroke-width="1""#.to_string()
+    } else {
+        format!(
+            r#" stroke-width="1" stroke-dasharray="1 {}""#,
+            spacing as f64 * SCALE - 1.0
+        )
+    }
+}
+
+/// Tells that all pixel coordinates are finite, as elements are only drawn when they are:
+/// anything else would end up in the image as `NaN` or `inf`.
+fn finite(points: &[(f64, f64)]) -> Option<()> {
+    if points.iter().all(|(x, y)| x.is_finite() && y.is_finite()) {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// Pixel coordinates as a list of points.
+fn coordinates(points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn polyline(points: &[(f64, f64)], colour: &str, stroke: &str) -> Option<String> {
+    finite(points)?;
+    Some(format!(
+        r#"<polyline points="{}" fill="none" stroke="{}"{} stroke-linejoin="round"/>"#,
+        coordinates(points),
+        colour,
+        stroke
+    ))
+}
+
+fn line(a: (f64, f64), b: (f64, f64), colour: &str, stroke: &str) -> Option<String> {
+    finite(&[a, b])?;
+    Some(format!(
+        r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}"{}/>"#,
+        a.0, a.1, b.0, b.1, colour, stroke
+    ))
+}
+
+/// Filled rectangle between two corners, translucent when `shaded`.
+fn rect(a: (f64, f64), b: (f64, f64), colour: &str, shaded: bool) -> Option<String> {
+    finite(&[a, b])?;
+    let opacity = if shaded { SHADE } else { 1.0 };
+    Some(format!(
+        r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}" fill-opacity="{}"/>"#,
+        a.0.min(b.0),
+        a.1.min(b.1),
+        (a.0 - b.0).abs(),
+        (a.1 - b.1).abs(),
+        colour,
+        opacity
+    ))
+}
+
+/// Circle around a point, filled or outlined.
+fn circle((x, y): (f64, f64), radius: f64, colour: &str, filled: bool) -> Option<String> {
+    finite(&[(x, y)])?;
+    let paint = if filled {
+        format!(r#"fill="{}""#, colour)
+    } else {
+        format!(r#"fill="none" stroke="{}" stroke-width="1.5""#, colour)
+    };
+    Some(format!(
+        r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" {}/>"#,
+        x, y, radius, paint
+    ))
+}
+
+/// Text vertically centered on a point, `anchor` telling which end of it is at the point.
+fn text((x, y): (f64, f64), anchor: &str, colour: &str, content: &str) -> Option<String> {
+    finite(&[(x, y)])?;
+    Some(format!(
+        r#"<text x="{:.1}" y="{:.1}" text-anchor="{}" dominant-baseline="central" fill="{}">{}</text>"#,
+        x,
+        y,
+        anchor,
+        colour,
+        escape(content)
+    ))
+}
+
+/// Marker of a scatter plot point.
+fn marker(point: (f64, f64), style: &Style, colour: &str) -> Option<String> {
+    finite(&[point])?;
+    let (x, y) = point;
+    let r = MARKER;
+    let solid = stroke(LineStyle::Solid);
+    match style.marker {
+        _ if style.marks_text() => {
+            let glyph = style.marker.glyph().to_string();
+            text(point, "middle", colour, &glyph)
+        }
+        Marker::Dot | Marker::Char(_) => circle(point, r / 3.0, colour, true),
+        Marker::Cross => Some(
+            [
+                line((x - r, y - r), (x + r, y + r), colour, &solid)?,
+                line((x - r, y + r), (x + r, y - r), colour, &solid)?,
+            ]
+            .join(""),
+        ),
+        Marker::Plus => Some(
+            [
+                line((x - r, y), (x + r, y), colour, &solid)?,
+                line((x, y - r), (x, y + r), colour, &solid)?,
+            ]
+            .join(""),
+        ),
+        Marker::Circle => circle(point, r, colour, false),
+        Marker::Square => Some(format!(
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="none" stroke="{}"{}/>"#,
+            x - r,
+            y - r,
+            2.0 * r,
+            2.0 * r,
+            colour,
+            solid
+        )),
+    }
+}
+
+/// Text with the characters reserved by XML escaped.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
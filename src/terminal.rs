@@ -0,0 +1,84 @@
+//! Fitting charts into the current terminal.
+
+use crate::{Chart, Plot, Point, Shape};
+use std::cmp;
+use terminal_size::{terminal_size, Height, Width};
+
+/// Canvas size used when the output is not a terminal.
+pub const DEFAULT_SIZE: (u32, u32) = (180, 60);
+
+/// Rows printed by [`Chart::display`](../struct.Chart.html#method.display) below the canvas.
+pub const AXIS_ROWS: u16 = 1;
+
+/// Smallest canvas side accepted by [`Chart::new`](../struct.Chart.html#method.new).
+const MIN_SIDE: u32 = 32;
+
+/// Returns the canvas size in points that fits into the current terminal.
+///
+/// Besides the x-axis label row, `reserved_rows` rows are kept free for anything printed
+/// around the chart: a title, the legend or the shell prompt. Next to the canvas,
+/// `label_columns` columns are kept free for the y-axis labels, as measured by
+/// [`Chart::y_label_columns`](../struct.Chart.html#method.y_label_columns).
+/// Every side is at least 32 points, so the result can always be passed to `Chart::new`.
+///
+/// Returns `None` when the output is not a terminal.
+pub fn canvas_size(reserved_rows: u16, label_columns: u16) -> Option<(u32, u32)> {
+    terminal_size().map(|(Width(cols), Height(rows))| fit(cols, rows, reserved_rows, label_columns))
+}
+
+/// Returns the canvas size of a chart of `shapes` over `x_range` (and `y_range`, where fixed)
+/// that fits into the current terminal, next to the y-axis labels and above `reserved_rows`
+/// as in [`canvas_size`]. Sides given in `size` are kept.
+///
+/// The y-axis labels depend on the y range, so the shapes are first plotted on a chart of
+/// the default size to measure them. Without a terminal, missing sides are those of
+/// [`DEFAULT_SIZE`].
+///
+/// ```
+/// use pointplots::terminal::chart_size;
+/// use pointplots::{Chart, Plot, Shape};
+///
+/// let shape = Shape::Continuous(Box::new(|x: f64| x.sin()));
+/// let (width, height) = chart_size((Some(120), None), (-3.0, 3.0), (None, None), &[&shape], 2);
+/// assert_eq!(120, width);
+/// Chart::new(width, height, -3.0, 3.0).lineplot(&shape).display();
+/// ```
+pub fn chart_size<'s>(
+    size: (Option<u32>, Option<u32>),
+    (xmin, xmax): (f64, f64),
+    (ymin, ymax): (Option<f64>, Option<f64>),
+    shapes: &[&'s Shape<'s, Point<f64, f64>>],
+    reserved_rows: u16,
+) -> (u32, u32) {
+    if let (Some(width), Some(height)) = size {
+        return (width, height);
+    }
+
+    let mut probe = Chart::new(DEFAULT_SIZE.0, DEFAULT_SIZE.1, xmin, xmax);
+    let mut plot = probe.y_range(ymin, ymax);
+    for shape in shapes {
+        plot = plot.lineplot(shape);
+    }
+    let label_columns = plot.y_label_columns() as u16;
+    let (width, height) = canvas_size(reserved_rows, label_columns).unwrap_or(DEFAULT_SIZE);
+
+    (size.0.unwrap_or(width), size.1.unwrap_or(height))
+}
+
+/// Translates terminal dimensions in characters into canvas dimensions in points.
+///
+/// A Braille character is 2 points wide and 4 points high, and the canvas prints one extra
+/// character past its width and height.
+///
+/// ```
+/// # use pointplots::terminal::fit;
+/// assert_eq!((134, 80), fit(80, 24, 2, 12));
+/// assert_eq!((150, 80), fit(80, 24, 2, 4));
+/// assert_eq!((32, 32), fit(20, 5, 2, 12));
+/// ```
+pub fn fit(cols: u16, rows: u16, reserved_rows: u16, label_columns: u16) -> (u32, u32) {
+    let cols = cols.saturating_sub(label_columns.saturating_add(1)) as u32;
+    let rows = rows.saturating_sub(AXIS_ROWS + reserved_rows + 1) as u32;
+
+    (cmp::max(cols * 2, MIN_SIDE), cmp::max(rows * 4, MIN_SIDE))
+}
@@ -2,7 +2,10 @@
 //!
 //! Merely a bunch of functions hanging around while the library API is taking shape.
 
-use crate::Point;
+use crate::{sampling, AxisValue, Point};
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+use std::ops::Range;
 
 /// Transforms points into frequency distribution (for using in histograms).
 /// Values outside of [`min`, `max`] interval are ignored, and everything that
@@ -12,7 +15,6 @@ use crate::Point;
 /// # use pointplots::{utils::histogram, Point};
 /// assert_eq!(vec![Point {x: 0.0, y: 1.0}, Point {x: 5.0, y: 1.0}], histogram( &[ (0.0, 0.0), (9.0, 9.0), (10.0, 10.0) ], 0.0, 10.0, 2 ));
 /// ```
-
 pub fn histogram(data: &[(f64, f64)], min: f64, max: f64, bins: usize) -> Vec<Point<f64, f64>> {
     let mut output = vec![0; bins];
 
@@ -25,7 +27,7 @@ pub fn histogram(data: &[(f64, f64)], min: f64, max: f64, bins: usize) -> Vec<Po
 
         let bucket_id = ((y - min) / step) as usize;
         if bucket_id < output.len() {
-            output[bucket_id as usize] += 1;
+            output[bucket_id] += 1;
         }
     }
 
@@ -41,8 +43,656 @@ pub fn histogram(data: &[(f64, f64)], min: f64, max: f64, bins: usize) -> Vec<Po
         .collect()
 }
 
+/// Largest number of bins of a histogram split by a rule, wider bins are made beyond.
+pub const MAX_BINS: usize = 10_000;
+
+/// How the range of a histogram is split into bins, into at most [`MAX_BINS`] of them
+/// unless their edges are given.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinRule {
+    /// Given number of bins of equal width.
+    Count(usize),
+    /// Bins of the given width, the last one reaching at or beyond the range.
+    Width(f64),
+    /// Bins between consecutive edges, the range is ignored. Edges are sorted, and those
+    /// repeated or not finite are dropped; with fewer than two left there are no bins, and
+    /// values are all counted as under- or overflow.
+    Edges(Vec<f64>),
+    /// `log2(n) + 1` bins, for about normally distributed values.
+    Sturges,
+    /// `sqrt(n)` bins.
+    Sqrt,
+    /// Bins `3.49 σ / ∛n` wide, for about normally distributed values.
+    Scott,
+    /// Bins `2 IQR / ∛n` wide, robust to outliers.
+    FreedmanDiaconis,
+}
+
+/// How bin counts are scaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Number (or total weight) of values in every bin.
+    Count,
+    /// Share of the values within the range, summing to 1.
+    Probability,
+    /// Share of the values within the range per unit of width, integrating to 1.
+    Density,
+}
+
+/// Frequency distribution of values, built with options.
+///
+/// Values equal to the upper end of the range fall into the last bin.
+///
+/// ```
+/// use pointplots::utils::{self, BinRule, Histogram, Normalization};
+/// use pointplots::{Chart, Plot, Shape};
+///
+/// let values = [1.0, 2.0, 2.5, 3.0, 3.5, 4.0, 10.0];
+/// let frequencies = Histogram::new(&values)
+///     .bins(BinRule::Count(3))
+///     .range(1.0, 4.0)
+///     .normalization(Normalization::Probability)
+///     .compute();
+/// assert_eq!(vec![1.0, 2.0, 3.0, 4.0], frequencies.edges);
+/// assert_eq!(vec![1.0 / 6.0, 2.0 / 6.0, 3.0 / 6.0], frequencies.counts);
+/// assert_eq!((0.0, 1.0), (frequencies.underflow, frequencies.overflow));
+///
+/// let tiny = Histogram::new(&values).bins(BinRule::Width(1e-12)).compute();
+/// assert_eq!(utils::MAX_BINS, tiny.counts.len());
+///
+/// let edges = vec![3.0, f64::NAN, 0.0, 2.0, 3.0];
+/// let uneven = Histogram::new(&values).bins(BinRule::Edges(edges)).compute();
+/// assert_eq!(vec![0.0, 2.0, 3.0], uneven.edges);
+/// assert_eq!(vec![1.0, 3.0], uneven.counts);
+/// assert_eq!((0.0, 3.0), (uneven.underflow, uneven.overflow));
+///
+/// let single = Histogram::new(&values).bins(BinRule::Edges(vec![2.0])).compute();
+/// assert!(single.counts.is_empty());
+/// assert_eq!((1.0, 6.0), (single.underflow, single.overflow));
+///
+/// Chart::<'_, f64, f64>::new(120, 60, 1.0, 4.0)
+///     .lineplot(&Shape::Bars(&frequencies.points()))
+///     .display();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Histogram<'a> {
+    values: &'a [f64],
+    weights: Option<&'a [f64]>,
+    bins: BinRule,
+    range: Option<(f64, f64)>,
+    normalization: Normalization,
+}
+
+/// Bins of a histogram along with their counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frequencies {
+    /// Edges of the bins in ascending order, one more than there are bins.
+    pub edges: Vec<f64>,
+    /// Scaled count of every bin.
+    pub counts: Vec<f64>,
+    /// Count (or total weight) of the values below the first edge, not scaled.
+    pub underflow: f64,
+    /// Count (or total weight) of the values above the last edge, not scaled.
+    pub overflow: f64,
+}
+
+impl<'a> Histogram<'a> {
+    /// Creates a histogram of counts over the range of the finite values, with bins
+    /// chosen by the Sturges rule.
+    pub fn new(values: &'a [f64]) -> Self {
+        Histogram {
+            values,
+            weights: None,
+            bins: BinRule::Sturges,
+            range: None,
+            normalization: Normalization::Count,
+        }
+    }
+
+    /// Weighs every value, values without a weight count as 1.
+    pub fn weights(mut self, weights: &'a [f64]) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    /// Sets how the range is split into bins.
+    pub fn bins(mut self, bins: BinRule) -> Self {
+        self.bins = bins;
+        self
+    }
+
+    /// Sets the range covered by the bins, values beyond are counted as under- or overflow.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min.min(max), min.max(max)));
+        self
+    }
+
+    /// Sets how the counts are scaled.
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Edges of the bins.
+    fn edges(&self) -> Vec<f64> {
+        if let BinRule::Edges(edges) = &self.bins {
+            let mut edges: Vec<_> = edges.iter().cloned().filter(|e| e.is_finite()).collect();
+            edges.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            edges.dedup();
+            return edges;
+        }
+
+        let sorted = sorted(self.values);
+        let (min, max) = self
+            .range
+            .unwrap_or_else(|| match (sorted.first(), sorted.last()) {
+                (Some(&min), Some(&max)) => (min, max),
+                _ => (0.0, 1.0),
+            });
+        let span = if max > min { max - min } else { 1.0 };
+        let n = sorted.len().max(1) as f64;
+        let spread = |width: f64| (span / width).ceil().max(1.0) as usize;
+
+        let count = match &self.bins {
+            BinRule::Count(count) => (*count).max(1),
+            BinRule::Width(width) if *width > 0.0 => spread(*width),
+            BinRule::Sqrt => n.sqrt().ceil() as usize,
+            BinRule::Scott | BinRule::FreedmanDiaconis if sorted.len() > 1 => {
+                let width = match self.bins {
+                    BinRule::Scott => {
+                        let mean = sorted.iter().sum::<f64>() / n;
+                        let variance =
+                            sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+                        3.49 * variance.sqrt() / n.cbrt()
+                    }
+                    _ => {
+                        let iqr = quantile_sorted(&sorted, 0.75) - quantile_sorted(&sorted, 0.25);
+                        2.0 * iqr / n.cbrt()
+                    }
+                };
+                if width > 0.0 {
+                    spread(width)
+                } else {
+                    1
+                }
+            }
+            _ => n.log2().ceil() as usize + 1,
+        }
+        .min(MAX_BINS);
+        let width = match &self.bins {
+            BinRule::Width(width) if *width > 0.0 && spread(*width) <= MAX_BINS => *width,
+            _ => span / count as f64,
+        };
+
+        (0..=count).map(|k| min + k as f64 * width).collect()
+    }
+
+    /// Counts the values into the bins.
+    pub fn compute(&self) -> Frequencies {
+        let edges = self.edges();
+        let mut counts = vec![0.0; edges.len().saturating_sub(1)];
+        let (mut underflow, mut overflow) = (0.0, 0.0);
+
+        // without edges, every value is beyond them
+        let first = edges.first().cloned().unwrap_or(f64::NEG_INFINITY);
+        let last = edges.last().cloned().unwrap_or(f64::NEG_INFINITY);
+        for (i, &v) in self.values.iter().enumerate() {
+            let weight = self.weights.and_then(|w| w.get(i)).cloned().unwrap_or(1.0);
+            if !v.is_finite() {
+                continue;
+            }
+            if v < first {
+                underflow += weight;
+            } else if v > last || counts.is_empty() {
+                overflow += weight;
+            } else {
+                // the upper edge of the range falls into the last bin
+                let bin = edges.partition_point(|&e| e <= v).saturating_sub(1);
+                let last_bin = counts.len() - 1;
+                counts[bin.min(last_bin)] += weight;
+            }
+        }
+
+        let total: f64 = counts.iter().sum();
+        if self.normalization != Normalization::Count && total != 0.0 {
+            for (bin, count) in counts.iter_mut().enumerate() {
+                *count /= total;
+                if self.normalization == Normalization::Density {
+                    *count /= edges[bin + 1] - edges[bin];
+                }
+            }
+        }
+
+        Frequencies {
+            edges,
+            counts,
+            underflow,
+            overflow,
+        }
+    }
+}
+
+impl Frequencies {
+    /// Points for plotting with `Shape::Bars`: every edge with the count of the bin
+    /// to its left, starting from zero at the first edge.
+    ///
+    /// ```
+    /// # use pointplots::utils::Frequencies;
+    /// let frequencies = Frequencies { edges: vec![0.0, 1.0, 2.0], counts: vec![3.0, 1.0], underflow: 0.0, overflow: 0.0 };
+    /// let heights: Vec<_> = frequencies.points().iter().map(|p| (p.x, p.y)).collect();
+    /// assert_eq!(vec![(0.0, 0.0), (1.0, 3.0), (2.0, 1.0)], heights);
+    /// ```
+    pub fn points(&self) -> Vec<Point<f64, f64>> {
+        self.edges
+            .iter()
+            .zip(std::iter::once(&0.0).chain(&self.counts))
+            .map(|(&x, &y)| Point { x, y })
+            .collect()
+    }
+}
+
+/// Transforms points into a two-dimensional frequency distribution (for using in heatmaps).
+/// Points outside of the `x` and `y` ranges or with a non-finite coordinate are ignored
+/// (as `Histogram` does with non-finite values), the ranges are split into `bins.0`
+/// and `bins.1` buckets of equal width, and points on the upper edge of a range fall into
+/// the last bucket. Returns rows of counts from the lowest `y` bucket up.
+///
+/// ```
+/// # use pointplots::utils::histogram2d;
+/// let data = [(0.0, 0.0), (1.0, 0.5), (9.0, 9.0), (10.0, 10.0), (f64::NAN, 0.0)];
+/// assert_eq!(vec![vec![2.0, 0.0], vec![0.0, 2.0]], histogram2d(&data, 0.0..10.0, 0.0..10.0, (2, 2)));
+/// ```
+pub fn histogram2d(
+    data: &[(f64, f64)],
+    x: Range<f64>,
+    y: Range<f64>,
+    bins: (usize, usize),
+) -> Vec<Vec<f64>> {
+    let mut output = vec![vec![0.0; bins.0]; bins.1];
+
+    let bucket = |v: f64, range: &Range<f64>, bins: usize| {
+        if !v.is_finite() || v < range.start || v > range.end || bins == 0 {
+            return None;
+        }
+        let step = (range.end - range.start) / bins as f64;
+        Some((((v - range.start) / step) as usize).min(bins - 1))
+    };
+
+    for &(px, py) in data.iter() {
+        if let (Some(i), Some(j)) = (bucket(px, &x, bins.0), bucket(py, &y, bins.1)) {
+            output[j][i] += 1.0;
+        }
+    }
+
+    output
+}
+
 pub fn f64s_into_points(data: &[(f64, f64)]) -> Vec<Point<f64, f64>> {
-    data.into_iter()
+    data.iter()
         .map(|(x, y)| -> Point<f64, f64> { Point { x: *x, y: *y } })
         .collect()
 }
+
+/// Collects anything convertible into points, such as `(x, y)` tuples or `[x, y]` arrays,
+/// by value or by reference.
+///
+/// ```
+/// # use pointplots::{utils::into_points, Point};
+/// let expected = vec![Point { x: 0.0, y: 1.0 }, Point { x: 1.0, y: 3.0 }];
+/// assert_eq!(expected, into_points(&[(0.0, 1.0), (1.0, 3.0)]));
+/// assert_eq!(expected, into_points(&vec![[0.0, 1.0], [1.0, 3.0]]));
+/// assert_eq!(expected, into_points((0..2).map(|i| (i as f64, 1.0 + 2.0 * i as f64))));
+/// ```
+pub fn into_points<T, U, P>(data: impl IntoIterator<Item = P>) -> Vec<Point<T, U>>
+where
+    T: AxisValue,
+    U: AxisValue,
+    P: Into<Point<T, U>>,
+{
+    data.into_iter().map(Into::into).collect()
+}
+
+/// Pairs y values with their indices as x values.
+///
+/// ```
+/// # use pointplots::{utils::ys_into_points, Point};
+/// assert_eq!(vec![Point { x: 0.0, y: 5.0 }, Point { x: 1.0, y: 7.0 }], ys_into_points(&[5.0, 7.0]));
+/// ```
+pub fn ys_into_points<U>(ys: &[U]) -> Vec<Point<f64, U>>
+where
+    U: AxisValue,
+{
+    ys.iter()
+        .enumerate()
+        .map(|(i, y)| Point {
+            x: i as f64,
+            y: y.clone(),
+        })
+        .collect()
+}
+
+/// Pairs x values with the y values at the same positions, up to the end of the shorter slice.
+///
+/// ```
+/// # use pointplots::{utils::slices_into_points, Point};
+/// assert_eq!(
+///     vec![Point { x: 1.0, y: 2.0 }, Point { x: 2.0, y: 4.0 }],
+///     slices_into_points(&[1.0, 2.0, 3.0], &[2.0, 4.0])
+/// );
+/// ```
+pub fn slices_into_points<T, U>(xs: &[T], ys: &[U]) -> Vec<Point<T, U>>
+where
+    T: AxisValue,
+    U: AxisValue,
+{
+    xs.iter()
+        .zip(ys)
+        .map(|(x, y)| Point {
+            x: x.clone(),
+            y: y.clone(),
+        })
+        .collect()
+}
+
+/// Finite values sorted in ascending order.
+fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<_> = values.iter().cloned().filter(|v| v.is_finite()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    sorted
+}
+
+/// Quantile `q` of sorted values, interpolated linearly between the closest ranks.
+pub(crate) fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    let rank = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+/// Quantile `q` (between 0 and 1) of the finite values, interpolated linearly between
+/// the closest ranks. Returns `None` if there are no finite values.
+///
+/// ```
+/// # use pointplots::utils::quantile;
+/// assert_eq!(Some(2.5), quantile(&[4.0, 1.0, 3.0, 2.0], 0.5));
+/// assert_eq!(None, quantile(&[f64::NAN], 0.5));
+/// ```
+pub fn quantile(values: &[f64], q: f64) -> Option<f64> {
+    let sorted = sorted(values);
+    if sorted.is_empty() {
+        None
+    } else {
+        Some(quantile_sorted(&sorted, q))
+    }
+}
+
+/// Percentiles (between 0 and 100) of the finite values, as points of the percentile
+/// and its value. Empty if there are no finite values.
+///
+/// ```
+/// # use pointplots::{utils::percentiles, Point};
+/// assert_eq!(
+///     vec![Point { x: 0.0, y: 1.0 }, Point { x: 50.0, y: 3.0 }, Point { x: 100.0, y: 5.0 }],
+///     percentiles(&[5.0, 1.0, 3.0, 2.0, 4.0], &[0.0, 50.0, 100.0])
+/// );
+/// ```
+pub fn percentiles(values: &[f64], percentiles: &[f64]) -> Vec<Point<f64, f64>> {
+    let sorted = sorted(values);
+    if sorted.is_empty() {
+        return vec![];
+    }
+
+    percentiles
+        .iter()
+        .map(|&p| Point {
+            x: p,
+            y: quantile_sorted(&sorted, p / 100.0),
+        })
+        .collect()
+}
+
+/// Empirical cumulative distribution of the finite values: the share of values
+/// at or below each value (for using with `Shape::Steps`).
+///
+/// ```
+/// # use pointplots::{utils::ecdf, Point};
+/// assert_eq!(
+///     vec![Point { x: 1.0, y: 0.25 }, Point { x: 2.0, y: 0.75 }, Point { x: 3.0, y: 1.0 }],
+///     ecdf(&[2.0, 1.0, 3.0, 2.0])
+/// );
+/// ```
+pub fn ecdf(values: &[f64]) -> Vec<Point<f64, f64>> {
+    let sorted = sorted(values);
+    let n = sorted.len() as f64;
+
+    let mut output: Vec<Point<f64, f64>> = vec![];
+    for (i, &v) in sorted.iter().enumerate() {
+        let y = (i + 1) as f64 / n;
+        match output.last_mut() {
+            Some(last) if last.x == v => last.y = y,
+            _ => output.push(Point { x: v, y }),
+        }
+    }
+
+    output
+}
+
+/// Width of the kernels of a density estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bandwidth {
+    /// Silverman's rule of thumb, robust to outliers.
+    Silverman,
+    /// Scott's rule of thumb.
+    Scott,
+    /// Fixed width.
+    Fixed(f64),
+}
+
+impl Bandwidth {
+    /// Width of the kernels for the sorted values.
+    fn width(&self, sorted: &[f64]) -> f64 {
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let sd =
+            (sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0)).sqrt();
+        let iqr = quantile_sorted(sorted, 0.75) - quantile_sorted(sorted, 0.25);
+
+        match self {
+            Bandwidth::Silverman => {
+                let spread = if iqr > 0.0 { sd.min(iqr / 1.34) } else { sd };
+                0.9 * spread * n.powf(-0.2)
+            }
+            Bandwidth::Scott => 1.06 * sd * n.powf(-0.2),
+            Bandwidth::Fixed(width) => *width,
+        }
+    }
+}
+
+/// Gaussian kernel density estimate of the finite values, at `points` evenly spaced
+/// positions reaching three bandwidths beyond the smallest and the largest value.
+///
+/// ```
+/// # use pointplots::utils::{kde, Bandwidth};
+/// let density = kde(&[-1.0, 0.0, 1.0], Bandwidth::Fixed(0.5), 201);
+/// let area: f64 = density.windows(2).map(|p| (p[1].x - p[0].x) * (p[0].y + p[1].y) / 2.0).sum();
+/// assert!((area - 1.0).abs() < 1e-3);
+/// ```
+pub fn kde(values: &[f64], bandwidth: Bandwidth, points: usize) -> Vec<Point<f64, f64>> {
+    let sorted = sorted(values);
+    if sorted.is_empty() || points == 0 {
+        return vec![];
+    }
+    let h = bandwidth.width(&sorted);
+    // identical values have no spread, fall back to a width relative to their magnitude
+    let h = if h > 0.0 {
+        h
+    } else {
+        sorted[0].abs().max(1.0) * 0.1
+    };
+
+    let (start, end) = (sorted[0] - 3.0 * h, sorted[sorted.len() - 1] + 3.0 * h);
+    let step = if points > 1 {
+        (end - start) / (points - 1) as f64
+    } else {
+        0.0
+    };
+    let norm = sorted.len() as f64 * h * (2.0 * PI).sqrt();
+
+    (0..points)
+        .map(|i| {
+            let x = start + i as f64 * step;
+            let y = sorted
+                .iter()
+                .map(|v| (-0.5 * ((x - v) / h).powi(2)).exp())
+                .sum::<f64>()
+                / norm;
+            Point { x, y }
+        })
+        .collect()
+}
+
+/// Running total of the y values.
+///
+/// ```
+/// # use pointplots::{utils::cumsum, Point};
+/// assert_eq!(
+///     vec![Point { x: 0.0, y: 1.0 }, Point { x: 1.0, y: 3.0 }, Point { x: 2.0, y: 6.0 }],
+///     cumsum(&[(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)])
+/// );
+/// ```
+pub fn cumsum(data: &[(f64, f64)]) -> Vec<Point<f64, f64>> {
+    let mut total = 0.0;
+    data.iter()
+        .map(|&(x, y)| {
+            total += y;
+            Point { x, y: total }
+        })
+        .collect()
+}
+
+/// Rescales the y values linearly so the smallest finite one is 0 and the largest is 1.
+/// All values become 0 if the finite ones are equal.
+///
+/// ```
+/// # use pointplots::{utils::normalize, Point};
+/// assert_eq!(
+///     vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, Point { x: 2.0, y: 0.25 }],
+///     normalize(&[(0.0, 2.0), (1.0, 6.0), (2.0, 3.0)])
+/// );
+/// ```
+pub fn normalize(data: &[(f64, f64)]) -> Vec<Point<f64, f64>> {
+    let finite = data.iter().map(|&(_, y)| y).filter(|y| y.is_finite());
+    let min = finite.clone().fold(f64::INFINITY, f64::min);
+    let max = finite.fold(f64::NEG_INFINITY, f64::max);
+
+    data.iter()
+        .map(|&(x, y)| Point {
+            x,
+            y: if max > min {
+                (y - min) / (max - min)
+            } else {
+                0.0
+            },
+        })
+        .collect()
+}
+
+/// Statistic of a rolling window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rolling {
+    /// Average of the window.
+    Mean,
+    /// Middle value of the window.
+    Median,
+    /// Smallest value of the window.
+    Min,
+    /// Largest value of the window.
+    Max,
+}
+
+/// Statistic of the y values in a window trailing each point, from the first point
+/// with a full window on. Values that are not finite are left out of the windows.
+///
+/// ```
+/// # use pointplots::{utils::{rolling, Rolling}, Point};
+/// let data = [(0.0, 1.0), (1.0, 5.0), (2.0, 3.0), (3.0, 7.0)];
+/// assert_eq!(
+///     vec![Point { x: 1.0, y: 3.0 }, Point { x: 2.0, y: 4.0 }, Point { x: 3.0, y: 5.0 }],
+///     rolling(&data, 2, Rolling::Mean)
+/// );
+/// assert_eq!(vec![5.0, 7.0], rolling(&data, 3, Rolling::Max).iter().map(|p| p.y).collect::<Vec<_>>());
+/// ```
+pub fn rolling(data: &[(f64, f64)], window: usize, statistic: Rolling) -> Vec<Point<f64, f64>> {
+    if window == 0 {
+        return vec![];
+    }
+
+    data.windows(window)
+        .map(|points| {
+            let values: Vec<_> = points.iter().map(|&(_, y)| y).collect();
+            let values = sorted(&values);
+            let y = if values.is_empty() {
+                f64::NAN
+            } else {
+                match statistic {
+                    Rolling::Mean => values.iter().sum::<f64>() / values.len() as f64,
+                    Rolling::Median => quantile_sorted(&values, 0.5),
+                    Rolling::Min => values[0],
+                    Rolling::Max => values[values.len() - 1],
+                }
+            };
+            Point {
+                x: points[window - 1].0,
+                y,
+            }
+        })
+        .collect()
+}
+
+/// How a chart thins out lines and steps with more points than its canvas is wide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Decimation {
+    /// Keeps the points [`decimate`] keeps, so lines cover the same dots as through all the
+    /// points, peaks included.
+    #[default]
+    MinMax,
+    /// Keeps about one point per column of dots picked by [`lttb`], which follows the shape
+    /// of the series with fewer points, but may cut narrow peaks.
+    Lttb,
+}
+
+/// Reduces a long series to the first, lowest, highest and last point of every run of
+/// consecutive points nearest to the same column of dots over `[xmin, xmax]`, so that
+/// lines through the result look the same on a canvas `columns` dots wide, peaks included.
+/// Charts do this by themselves for lines and steps with more points than the canvas width,
+/// unless set to [`Decimation::Lttb`].
+///
+/// ```
+/// # use pointplots::utils::decimate;
+/// let data: Vec<_> = (0..10_000).map(|i| (i as f64, (i as f64 / 100.0).sin())).collect();
+/// let decimated = decimate(&data, 0.0, 10_000.0, 100);
+/// assert!(decimated.len() <= 400);
+/// assert_eq!(data[0].1, decimated[0].y);
+/// assert_eq!(1.0, decimated.iter().map(|p| p.y.round()).fold(f64::MIN, f64::max));
+/// ```
+pub fn decimate(data: &[(f64, f64)], xmin: f64, xmax: f64, columns: u32) -> Vec<Point<f64, f64>> {
+    f64s_into_points(&sampling::decimate(data, xmin, xmax, columns))
+}
+
+/// Picks `threshold` points of a series ordered along the x-axis with the
+/// [Largest-Triangle-Three-Buckets](https://skemman.is/handle/1946/15343) algorithm,
+/// which keeps its visual shape. Points with a non-finite coordinate are left out, and
+/// at least 3 points are picked: the first, the last and one in between. Charts set to
+/// [`Decimation::Lttb`] thin out long lines and steps with it.
+///
+/// ```
+/// # use pointplots::utils::lttb;
+/// let data: Vec<_> = (0..1000).map(|i| (i as f64, (i as f64 / 50.0).cos())).collect();
+/// let sampled = lttb(&data, 50);
+/// assert_eq!(50, sampled.len());
+/// assert_eq!((0.0, 999.0), (sampled[0].x, sampled[49].x));
+/// assert_eq!(3, lttb(&data, 1).len());
+/// ```
+pub fn lttb(data: &[(f64, f64)], threshold: usize) -> Vec<Point<f64, f64>> {
+    f64s_into_points(&sampling::lttb(data, threshold))
+}